@@ -70,3 +70,28 @@ pub fn local_testnet_config() -> ChainSpec {
 	.with_properties(properties)
 	.build()
 }
+
+/// A local chain starting with thousands of votes on posts that are about to expire, for profiling block import and
+/// the weight of resolving and ending posts.
+pub fn stress_config() -> ChainSpec {
+	// Give your base currency a unit name and decimal places
+	let mut properties = sc_chain_spec::Properties::new();
+	properties.insert("tokenSymbol".into(), "UNIT".into());
+	properties.insert("tokenDecimals".into(), 12.into());
+	properties.insert("ss58Format".into(), 42.into());
+
+	ChainSpec::builder(
+		runtime::WASM_BINARY.expect("WASM binary was not built, please build it!"),
+		Extensions {
+			relay_chain: "rococo-local".into(),
+			// You MUST set this to the correct network!
+			para_id: 1000,
+		},
+	)
+	.with_name("Bullposting Stress")
+	.with_id("bullposting_stress")
+	.with_chain_type(ChainType::Local)
+	.with_genesis_config_preset_name(runtime::BULLPOSTING_STRESS_PRESET)
+	.with_properties(properties)
+	.build()
+}
@@ -7,8 +7,9 @@
 
 use std::sync::Arc;
 
-use parachain_bullposting_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use parachain_bullposting_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Nonce};
 
+use sc_client_api::Backend;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -18,16 +19,18 @@ use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 pub type RpcExtension = jsonrpsee::RpcModule<()>;
 
 /// Full client dependencies
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, B, P> {
 	/// The client instance to use.
 	pub client: Arc<C>,
+	/// The backend instance to use, for reading the offchain index.
+	pub backend: Arc<B>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
 }
 
 /// Instantiate all RPC extensions.
-pub fn create_full<C, P>(
-	deps: FullDeps<C, P>,
+pub fn create_full<C, B, P>(
+	deps: FullDeps<C, B, P>,
 ) -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>
@@ -38,16 +41,21 @@ where
 		+ 'static,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+	C::Api: pallet_parachain_bullposting_rpc::BullpostingRuntimeApi<Block, AccountId, Balance, BlockNumber>,
 	C::Api: BlockBuilder<Block>,
+	B: Backend<Block> + Send + Sync + 'static,
+	B::OffchainStorage: 'static,
 	P: TransactionPool + Sync + Send + 'static,
 {
+	use pallet_parachain_bullposting_rpc::{Bullposting, BullpostingApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut module = RpcExtension::new(());
-	let FullDeps { client, pool } = deps;
+	let FullDeps { client, backend, pool } = deps;
 
 	module.merge(System::new(client.clone(), pool).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(Bullposting::new(client, backend.offchain_storage()).into_rpc())?;
 	Ok(module)
 }
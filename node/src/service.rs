@@ -304,11 +304,15 @@ pub async fn start_parachain_node(
 
 	let rpc_builder = {
 		let client = client.clone();
+		let backend = backend.clone();
 		let transaction_pool = transaction_pool.clone();
 
 		Box::new(move |_| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: transaction_pool.clone() };
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				backend: backend.clone(),
+				pool: transaction_pool.clone(),
+			};
 
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
@@ -0,0 +1,251 @@
+//! RPC interface for the bullposting pallet.
+//!
+//! Serves the `bullposting_*` methods by calling into the `BullpostingApi` runtime API. Every method
+//! takes an optional block hash and falls back to the best block, except `bullposting_getPostText`,
+//! which reads the offchain index instead of the chain state.
+//!
+//! Balances are returned as `NumberOrHex`, so that amounts which don't fit in a JSON number
+//! (eg. `u128` balances) are sent as hex strings instead of losing precision in JS clients.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{ErrorObject, ErrorObjectOwned},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{offchain::OffchainStorage, H256};
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_parachain_bullposting_runtime_api::{
+    BullpostingApi as BullpostingRuntimeApi, BullpostingParameters, PostDetails, ResolutionPreview, VoteChoice,
+};
+
+/// Bullposting RPC methods.
+/// `Balance` is the runtime's balance type, every balance is returned as a `NumberOrHex`.
+#[rpc(client, server)]
+pub trait BullpostingApi<BlockHash, AccountId, Balance, BlockNumber> {
+    /// A post by its ID, or `None` if it doesn't exist.
+    #[method(name = "bullposting_getPost")]
+    fn get_post(&self, id: H256, at: Option<BlockHash>) -> RpcResult<Option<PostDetails<AccountId, NumberOrHex, BlockNumber>>>;
+
+    /// An account's votes, as (post ID, amount, direction).
+    #[method(name = "bullposting_getVotes")]
+    fn get_votes(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<(H256, NumberOrHex, VoteChoice<NumberOrHex>)>>;
+
+    /// The accounts that voted on a post.
+    #[method(name = "bullposting_getVoters")]
+    fn get_voters(&self, id: H256, at: Option<BlockHash>) -> RpcResult<Vec<AccountId>>;
+
+    /// The result, reward and slash the post's voting would resolve to with its current votes.
+    /// Returns `None` if the post doesn't exist or was already resolved.
+    #[method(name = "bullposting_previewResolution")]
+    fn preview_resolution(&self, id: H256, at: Option<BlockHash>) -> RpcResult<Option<ResolutionPreview<NumberOrHex>>>;
+
+    /// The ID of the post submitted with this text, or `None` if no such post exists.
+    #[method(name = "bullposting_postIdFromText")]
+    fn post_id_from_text(&self, text: String, at: Option<BlockHash>) -> RpcResult<Option<H256>>;
+
+    /// The parameters new posts are submitted under.
+    #[method(name = "bullposting_getParameters")]
+    fn get_parameters(&self, at: Option<BlockHash>) -> RpcResult<BullpostingParameters<NumberOrHex, BlockNumber>>;
+
+    /// The full text of a post, or `None` if it wasn't indexed.
+    /// Requires the node to run with `--enable-offchain-indexing true`.
+    #[method(name = "bullposting_getPostText")]
+    fn get_post_text(&self, id: H256) -> RpcResult<Option<String>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+    /// The call to the runtime failed.
+    RuntimeError,
+    /// The node doesn't have offchain storage.
+    OffchainStorageUnavailable,
+}
+
+impl From<Error> for i32 {
+    fn from(e: Error) -> i32 {
+        match e {
+            Error::RuntimeError => 1,
+            Error::OffchainStorageUnavailable => 2,
+        }
+    }
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> ErrorObjectOwned {
+    ErrorObject::owned(Error::RuntimeError.into(), "Unable to query the bullposting runtime API.", Some(format!("{:?}", e)))
+}
+
+fn post_details<AccountId, Balance: Into<NumberOrHex>, BlockNumber>(
+    post: PostDetails<AccountId, Balance, BlockNumber>,
+) -> PostDetails<AccountId, NumberOrHex, BlockNumber> {
+    PostDetails {
+        id: post.id,
+        submitter: post.submitter,
+        bond: post.bond.into(),
+        bull_votes: post.bull_votes.into(),
+        bear_votes: post.bear_votes.into(),
+        abstain_votes: post.abstain_votes.into(),
+        bull_weight: post.bull_weight.into(),
+        bear_weight: post.bear_weight.into(),
+        voting_until: post.voting_until,
+        resolved: post.resolved,
+        voter_count: post.voter_count,
+    }
+}
+
+fn vote_choice<Balance: Into<NumberOrHex>>(vote: VoteChoice<Balance>) -> VoteChoice<NumberOrHex> {
+    match vote {
+        VoteChoice::Aye => VoteChoice::Aye,
+        VoteChoice::Nay => VoteChoice::Nay,
+        VoteChoice::Abstain => VoteChoice::Abstain,
+        VoteChoice::Split { bull, bear } => VoteChoice::Split { bull: bull.into(), bear: bear.into() },
+    }
+}
+
+fn resolution_preview<Balance: Into<NumberOrHex>>(preview: ResolutionPreview<Balance>) -> ResolutionPreview<NumberOrHex> {
+    ResolutionPreview {
+        result: preview.result,
+        reward: preview.reward.into(),
+        slash: preview.slash.into(),
+        voter_share: preview.voter_share.into(),
+        turnout: preview.turnout.into(),
+        voter_count: preview.voter_count,
+    }
+}
+
+fn parameters<Balance: Into<NumberOrHex>, BlockNumber>(
+    parameters: BullpostingParameters<Balance, BlockNumber>,
+) -> BullpostingParameters<NumberOrHex, BlockNumber> {
+    BullpostingParameters {
+        reward_style: parameters.reward_style,
+        flat_reward: parameters.flat_reward.into(),
+        reward_coefficient: parameters.reward_coefficient,
+        slash_style: parameters.slash_style,
+        flat_slash: parameters.flat_slash.into(),
+        slash_coefficient: parameters.slash_coefficient,
+        voting_period: parameters.voting_period,
+        bond_minimum: parameters.bond_minimum.into(),
+        vote_minimum: parameters.vote_minimum.into(),
+        quorum_votes: parameters.quorum_votes.into(),
+        quorum_voters: parameters.quorum_voters,
+        withdrawal_fee: parameters.withdrawal_fee.into(),
+        storage_rent: parameters.storage_rent.into(),
+        voter_payouts: parameters.voter_payouts,
+        voter_reward_cut: parameters.voter_reward_cut,
+        commit_reveal: parameters.commit_reveal,
+        reveal_period: parameters.reveal_period,
+        unrevealed_forfeit: parameters.unrevealed_forfeit,
+        tally_mode: parameters.tally_mode,
+    }
+}
+
+/// Provides RPC methods to query the bullposting pallet.
+pub struct Bullposting<C, Block, S> {
+    client: Arc<C>,
+    offchain_db: Option<S>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block, S> Bullposting<C, Block, S> {
+    /// Creates a new instance of the Bullposting RPC handler.
+    /// `offchain_db` is the node's offchain storage, used to look up the full text of posts.
+    pub fn new(client: Arc<C>, offchain_db: Option<S>) -> Self {
+        Self { client, offchain_db, _marker: Default::default() }
+    }
+}
+
+impl<C, Block, S> Bullposting<C, Block, S>
+where
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    // The given block, or the best block if none was given
+    fn at(&self, at: Option<Block::Hash>) -> Block::Hash {
+        at.unwrap_or_else(|| self.client.info().best_hash)
+    }
+}
+
+impl<C, Block, S, AccountId, Balance, BlockNumber> BullpostingApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber>
+    for Bullposting<C, Block, S>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    S: OffchainStorage + 'static,
+    C::Api: BullpostingRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+    Balance: Codec + Into<NumberOrHex> + Send + Sync + 'static,
+    BlockNumber: Codec + Serialize + Send + Sync + 'static,
+{
+    fn get_post(
+        &self,
+        id: H256,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<PostDetails<AccountId, NumberOrHex, BlockNumber>>> {
+        let post = self.client.runtime_api().post(self.at(at), id.into()).map_err(runtime_error)?;
+
+        Ok(post.map(post_details))
+    }
+
+    fn get_votes(
+        &self,
+        who: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<(H256, NumberOrHex, VoteChoice<NumberOrHex>)>> {
+        let votes = self.client.runtime_api().votes(self.at(at), who).map_err(runtime_error)?;
+
+        Ok(votes.into_iter().map(|(id, amount, vote)| (id.into(), amount.into(), vote_choice(vote))).collect())
+    }
+
+    fn get_voters(&self, id: H256, at: Option<Block::Hash>) -> RpcResult<Vec<AccountId>> {
+        self.client.runtime_api().voters(self.at(at), id.into()).map_err(runtime_error)
+    }
+
+    fn preview_resolution(&self, id: H256, at: Option<Block::Hash>) -> RpcResult<Option<ResolutionPreview<NumberOrHex>>> {
+        let preview = self.client.runtime_api().preview_resolution(self.at(at), id.into()).map_err(runtime_error)?;
+
+        Ok(preview.map(resolution_preview))
+    }
+
+    fn post_id_from_text(&self, text: String, at: Option<Block::Hash>) -> RpcResult<Option<H256>> {
+        let post = self
+            .client
+            .runtime_api()
+            .post_by_input(self.at(at), text.into_bytes())
+            .map_err(runtime_error)?;
+
+        Ok(post.map(|post| post.id.into()))
+    }
+
+    fn get_parameters(&self, at: Option<Block::Hash>) -> RpcResult<BullpostingParameters<NumberOrHex, BlockNumber>> {
+        let current = self.client.runtime_api().parameters(self.at(at)).map_err(runtime_error)?;
+
+        Ok(parameters(current))
+    }
+
+    fn get_post_text(&self, id: H256) -> RpcResult<Option<String>> {
+        let offchain_db = self.offchain_db.as_ref().ok_or_else(|| {
+            ErrorObject::owned(
+                Error::OffchainStorageUnavailable.into(),
+                "The node doesn't have offchain storage.",
+                None::<()>,
+            )
+        })?;
+
+        let text = offchain_db.get(
+            sp_core::offchain::STORAGE_PREFIX,
+            &pallet_parachain_bullposting::offchain_post_key(&id.into()),
+        );
+
+        Ok(text.map(|text| String::from_utf8_lossy(&text).into_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,233 @@
+use super::*;
+use pallet_parachain_bullposting::{Outcome, TallyMode};
+use sp_api::ApiRef;
+use sp_blockchain::{BlockStatus, Info};
+use sp_core::offchain::storage::InMemOffchainStorage;
+use sp_runtime::{generic, traits::BlakeTwo256, OpaqueExtrinsic, Percent};
+
+type Block = generic::Block<generic::Header<u32, BlakeTwo256>, OpaqueExtrinsic>;
+type Rpc = Bullposting<TestClient, Block, InMemOffchainStorage>;
+
+// Too large for a JSON number to hold without losing precision
+const BIG: u128 = 1 << 100;
+const POST_ID: [u8; 32] = [1; 32];
+const POST_TEXT: &str = "To the moon";
+
+fn test_post() -> PostDetails<u64, u128, u32> {
+    PostDetails {
+        id: POST_ID,
+        submitter: 1,
+        bond: BIG,
+        bull_votes: BIG + 1,
+        bear_votes: 10,
+        abstain_votes: 0,
+        bull_weight: BIG + 1,
+        bear_weight: 10,
+        voting_until: 100,
+        resolved: false,
+        voter_count: 2,
+    }
+}
+
+struct TestClient;
+
+struct TestRuntimeApi;
+
+sp_api::mock_impl_runtime_apis! {
+    impl BullpostingRuntimeApi<Block, u64, u128, u32> for TestRuntimeApi {
+        fn pot_balance() -> Option<u128> {
+            Some(BIG)
+        }
+
+        fn post(id: [u8; 32]) -> Option<PostDetails<u64, u128, u32>> {
+            (id == POST_ID).then(test_post)
+        }
+
+        fn post_by_input(post_input: Vec<u8>) -> Option<PostDetails<u64, u128, u32>> {
+            (post_input == POST_TEXT.as_bytes()).then(test_post)
+        }
+
+        fn votes(_who: u64) -> Vec<([u8; 32], u128, VoteChoice<u128>)> {
+            vec![(POST_ID, BIG, VoteChoice::Split { bull: BIG - 10, bear: 10 })]
+        }
+
+        fn voters(_id: [u8; 32]) -> Vec<u64> {
+            vec![1, 2]
+        }
+
+        fn preview_resolution(_id: [u8; 32]) -> Option<ResolutionPreview<u128>> {
+            Some(ResolutionPreview {
+                result: Outcome::Bullish,
+                reward: BIG,
+                slash: 0,
+                voter_share: 0,
+                turnout: BIG + 11,
+                voter_count: 2,
+            })
+        }
+
+        fn parameters() -> BullpostingParameters<u128, u32> {
+            BullpostingParameters {
+                reward_style: false,
+                flat_reward: BIG,
+                reward_coefficient: 100,
+                slash_style: false,
+                flat_slash: 5,
+                slash_coefficient: 100,
+                voting_period: 100,
+                bond_minimum: 1,
+                vote_minimum: 1,
+                quorum_votes: 0,
+                quorum_voters: 0,
+                withdrawal_fee: 0,
+                storage_rent: 0,
+                voter_payouts: false,
+                voter_reward_cut: Percent::zero(),
+                commit_reveal: false,
+                reveal_period: 0,
+                unrevealed_forfeit: Percent::zero(),
+                tally_mode: TallyMode::Linear,
+            }
+        }
+    }
+}
+
+impl ProvideRuntimeApi<Block> for TestClient {
+    type Api = TestRuntimeApi;
+
+    fn runtime_api(&self) -> ApiRef<'_, Self::Api> {
+        TestRuntimeApi.into()
+    }
+}
+
+impl HeaderBackend<Block> for TestClient {
+    fn header(&self, _hash: H256) -> sp_blockchain::Result<Option<<Block as BlockT>::Header>> {
+        Ok(None)
+    }
+
+    fn info(&self) -> Info<Block> {
+        Info {
+            best_hash: H256::repeat_byte(1),
+            best_number: 1,
+            genesis_hash: H256::zero(),
+            finalized_hash: H256::zero(),
+            finalized_number: 0,
+            finalized_state: None,
+            number_leaves: 1,
+            block_gap: None,
+        }
+    }
+
+    fn status(&self, _hash: H256) -> sp_blockchain::Result<BlockStatus> {
+        Ok(BlockStatus::InChain)
+    }
+
+    fn number(&self, _hash: H256) -> sp_blockchain::Result<Option<u32>> {
+        Ok(None)
+    }
+
+    fn hash(&self, _number: u32) -> sp_blockchain::Result<Option<H256>> {
+        Ok(None)
+    }
+}
+
+fn new_rpc() -> Rpc {
+    let mut offchain_db = InMemOffchainStorage::default();
+    offchain_db.set(
+        sp_core::offchain::STORAGE_PREFIX,
+        &pallet_parachain_bullposting::offchain_post_key(&POST_ID),
+        POST_TEXT.as_bytes(),
+    );
+
+    Bullposting::new(Arc::new(TestClient), Some(offchain_db))
+}
+
+fn hex(value: u128) -> serde_json::Value {
+    serde_json::to_value(NumberOrHex::from(value)).unwrap()
+}
+
+#[test]
+fn test_get_post() {
+    let rpc = new_rpc();
+
+    let post = BullpostingApiServer::<H256, u64, u128, u32>::get_post(&rpc, H256(POST_ID), None).unwrap().unwrap();
+    assert_eq!(post.bond, NumberOrHex::from(BIG));
+    assert_eq!(post.bull_votes, NumberOrHex::from(BIG + 1));
+    assert_eq!(post.voting_until, 100);
+
+    // Balances too large for a JSON number are sent as hex strings
+    let json = serde_json::to_value(&post).unwrap();
+    assert_eq!(json["bond"], serde_json::json!("0x10000000000000000000000000"));
+    assert_eq!(json["bear_votes"], hex(10));
+
+    let missing = BullpostingApiServer::<H256, u64, u128, u32>::get_post(&rpc, H256::repeat_byte(2), None).unwrap();
+    assert_eq!(missing, None);
+}
+
+#[test]
+fn test_get_votes() {
+    let rpc = new_rpc();
+
+    let votes = BullpostingApiServer::<H256, u64, u128, u32>::get_votes(&rpc, 1, None).unwrap();
+    assert_eq!(
+        votes,
+        vec![(
+            H256(POST_ID),
+            NumberOrHex::from(BIG),
+            VoteChoice::Split { bull: NumberOrHex::from(BIG - 10), bear: NumberOrHex::from(10u128) },
+        )]
+    );
+
+    let voters = BullpostingApiServer::<H256, u64, u128, u32>::get_voters(&rpc, H256(POST_ID), None).unwrap();
+    assert_eq!(voters, vec![1, 2]);
+}
+
+#[test]
+fn test_preview_resolution() {
+    let rpc = new_rpc();
+
+    let preview =
+        BullpostingApiServer::<H256, u64, u128, u32>::preview_resolution(&rpc, H256(POST_ID), None).unwrap().unwrap();
+    assert_eq!(preview.result, Outcome::Bullish);
+    assert_eq!(preview.reward, NumberOrHex::from(BIG));
+    assert_eq!(preview.turnout, NumberOrHex::from(BIG + 11));
+    assert_eq!(preview.voter_count, 2);
+}
+
+#[test]
+fn test_get_parameters() {
+    let rpc = new_rpc();
+
+    let parameters = BullpostingApiServer::<H256, u64, u128, u32>::get_parameters(&rpc, None).unwrap();
+    assert_eq!(parameters.flat_reward, NumberOrHex::from(BIG));
+    assert_eq!(parameters.flat_slash, NumberOrHex::from(5u128));
+    assert_eq!(parameters.voting_period, 100);
+    assert_eq!(parameters.tally_mode, TallyMode::Linear);
+}
+
+#[test]
+fn test_post_id_from_text() {
+    let rpc = new_rpc();
+
+    let id = BullpostingApiServer::<H256, u64, u128, u32>::post_id_from_text(&rpc, POST_TEXT.into(), None).unwrap();
+    assert_eq!(id, Some(H256(POST_ID)));
+
+    let id = BullpostingApiServer::<H256, u64, u128, u32>::post_id_from_text(&rpc, "Rug".into(), None).unwrap();
+    assert_eq!(id, None);
+}
+
+#[test]
+fn test_get_post_text() {
+    let rpc = new_rpc();
+
+    let text = BullpostingApiServer::<H256, u64, u128, u32>::get_post_text(&rpc, H256(POST_ID)).unwrap();
+    assert_eq!(text, Some(POST_TEXT.into()));
+
+    let text = BullpostingApiServer::<H256, u64, u128, u32>::get_post_text(&rpc, H256::repeat_byte(2)).unwrap();
+    assert_eq!(text, None);
+
+    // Fails if the node doesn't have offchain storage
+    let rpc: Rpc = Bullposting::new(Arc::new(TestClient), None);
+    let err = BullpostingApiServer::<H256, u64, u128, u32>::get_post_text(&rpc, H256(POST_ID)).unwrap_err();
+    assert_eq!(err.code(), i32::from(Error::OffchainStorageUnavailable));
+}
@@ -0,0 +1,46 @@
+//! Runtime API definition for the bullposting pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+
+pub use pallet_parachain_bullposting::{BullpostingParameters, Outcome, PostDetails, ResolutionPreview, VoteChoice};
+
+sp_api::decl_runtime_apis! {
+    /// Read-only access to the bullposting pallet's state.
+    ///
+    /// Version 2 made the API generic over the account ID and block number as well as the balance,
+    /// and added every method other than `pot_balance`.
+    #[api_version(2)]
+    pub trait BullpostingApi<AccountId, Balance, BlockNumber> where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// The balance of the pot that slashes go into and rewards come out of.
+        /// Returns `None` if the runtime doesn't use a pot.
+        fn pot_balance() -> Option<Balance>;
+
+        /// A post by its ID, or `None` if it doesn't exist.
+        fn post(id: [u8; 32]) -> Option<PostDetails<AccountId, Balance, BlockNumber>>;
+
+        /// A post by its input text, hashed the same way as the extrinsics do.
+        fn post_by_input(post_input: Vec<u8>) -> Option<PostDetails<AccountId, Balance, BlockNumber>>;
+
+        /// An account's votes, as (post ID, amount, direction).
+        fn votes(who: AccountId) -> Vec<([u8; 32], Balance, VoteChoice<Balance>)>;
+
+        /// The accounts that voted on a post.
+        fn voters(id: [u8; 32]) -> Vec<AccountId>;
+
+        /// The result, reward and slash the post's voting would resolve to with its current votes.
+        /// Returns `None` if the post doesn't exist or was already resolved.
+        fn preview_resolution(id: [u8; 32]) -> Option<ResolutionPreview<Balance>>;
+
+        /// The parameters new posts are submitted under.
+        fn parameters() -> BullpostingParameters<Balance, BlockNumber>;
+    }
+}
@@ -6,13 +6,14 @@ use super::*;
 use crate::Pallet as BullPosting;
 use frame_benchmarking::v2::*;
 use frame_system::RawOrigin;
-use frame_support::traits::{Get, fungible::{Inspect, Mutate}};
+use frame_support::traits::{EnsureOrigin, Get, fungible::{Inspect, Mutate}};
 use frame_support::sp_runtime::*;
 use crate::benchmarking::traits::{Zero, One};
 
 const SEED: u32 = 0;
 const MAX_URL: usize = 2000;
 const MAX_VOTERS: u32 = 2000;
+const MAX_RESOLUTIONS: u32 = 100;
 
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
@@ -35,10 +36,10 @@ mod benchmarks {
 		<T as pallet::Config>::NativeBalance::set_balance(&caller, balance);
 
 		#[extrinsic_call]
-		try_submit_post(RawOrigin::Signed(caller.clone()), post, bond.clone());
+		try_submit_post(RawOrigin::Signed(caller.clone()), post, bond);
 
 		let voting_until = frame_system::Pallet::<T>::block_number() +
-            T::VotingPeriod::get();
+            CurrentParameters::<T>::get().voting_period;
 
 		assert_last_event::<T>(Event::PostSubmitted {
 			id: post_id,
@@ -67,13 +68,13 @@ mod benchmarks {
 		BullPosting::<T>::try_submit_post(RawOrigin::Signed(alice.clone()).into(), post.clone(), bond)?;
 
         #[extrinsic_call]
-		try_submit_vote(RawOrigin::Signed(bob.clone()), post, vote_amount, Direction::Bullish);
+		try_submit_vote(RawOrigin::Signed(bob.clone()), post, vote_amount, VoteChoice::Aye);
 
 		assert_last_event::<T>(Event::VoteSubmitted {
 			id: post_id,
 			voter: bob,
 			vote_amount,
-			direction: Direction::Bullish,
+			vote: VoteChoice::Aye,
 		}.into());
 		Ok(())
 	}
@@ -95,16 +96,16 @@ mod benchmarks {
 		<T as pallet::Config>::NativeBalance::set_balance(&bob, balance);
 
 		BullPosting::<T>::try_submit_post(RawOrigin::Signed(alice.clone()).into(), post.clone(), bond)?;
-		BullPosting::<T>::try_submit_vote(RawOrigin::Signed(bob.clone()).into(), post.clone(), vote_amount, Direction::Bullish)?;
+		BullPosting::<T>::try_submit_vote(RawOrigin::Signed(bob.clone()).into(), post.clone(), vote_amount, VoteChoice::Aye)?;
 
         #[extrinsic_call]
-		try_update_vote(RawOrigin::Signed(bob.clone()), post, new_vote_amount, Direction::Bearish);
+		try_update_vote(RawOrigin::Signed(bob.clone()), post, new_vote_amount, VoteChoice::Nay);
 
 		assert_last_event::<T>(Event::VoteUpdated {
 			id: post_id,
 			voter: bob,
 			vote_amount: new_vote_amount,
-			direction: Direction::Bearish,
+			vote: VoteChoice::Nay,
 		}.into());
 		Ok(())
 	}
@@ -124,23 +125,31 @@ mod benchmarks {
 		<T as pallet::Config>::NativeBalance::set_balance(&alice, balance);
 		<T as pallet::Config>::NativeBalance::set_balance(&bob, balance);
 
+		// Make sure the quorum is reached, so the post is rewarded
+		CurrentParameters::<T>::mutate(|parameters| {
+			parameters.quorum_votes = Zero::zero();
+			parameters.quorum_voters = 0;
+		});
+
 		BullPosting::<T>::try_submit_post(RawOrigin::Signed(alice.clone()).into(), post.clone(), bond)?;
-		BullPosting::<T>::try_submit_vote(RawOrigin::Signed(bob.clone()).into(), post.clone(), vote_amount, Direction::Bullish)?;
+		BullPosting::<T>::try_submit_vote(RawOrigin::Signed(bob.clone()).into(), post.clone(), vote_amount, VoteChoice::Aye)?;
 
 		let new_block_num = frame_system::Pallet::<T>::block_number() +
-		T::VotingPeriod::get() + One::one();
+		CurrentParameters::<T>::get().voting_period + One::one();
 
 		frame_system::Pallet::<T>::set_block_number(new_block_num);
 
         #[extrinsic_call]
 		try_resolve_voting(RawOrigin::Signed(bob.clone()), post);
 		
-		assert_last_event::<T>(Event::PostEnded {
+		assert_last_event::<T>(Event::VotingResolved {
 			id: post_id,
 			submitter: alice,
-			result: Direction::Bullish,
+			result: Outcome::Bullish,
 			rewarded: bond,
 			slashed: Zero::zero(),
+			turnout: vote_amount,
+			voter_count: 1,
 		}.into());
 
 		Ok(())
@@ -169,11 +178,11 @@ mod benchmarks {
 		for i in 0..x {
 			let acc: T::AccountId = account("filler", i, SEED);
 			<T as pallet::Config>::NativeBalance::set_balance(&acc, balance);
-			BullPosting::<T>::try_submit_vote(RawOrigin::Signed(acc).into(), post.clone(), vote_amount, Direction::Bullish)?;
+			BullPosting::<T>::try_submit_vote(RawOrigin::Signed(acc).into(), post.clone(), vote_amount, VoteChoice::Aye)?;
 		}
 
 		let new_block_num = frame_system::Pallet::<T>::block_number() +
-		T::VotingPeriod::get() + One::one();
+		CurrentParameters::<T>::get().voting_period + One::one();
 
 		frame_system::Pallet::<T>::set_block_number(new_block_num);
 
@@ -184,7 +193,7 @@ mod benchmarks {
 
 		// assert that the post is partially or fully resolved
 		if x >= 1000 {
-			assert_last_event::<T>(Event::PartiallyResolved {
+			assert_last_event::<T>(Event::PostPartiallyEnded {
 				id: post_id
 			}.into())
 		} else {
@@ -193,5 +202,330 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn on_initialize_resolve(
+		x: Linear<0, MAX_RESOLUTIONS>
+	) -> Result<(), BenchmarkError> {
+		let alice: T::AccountId = account("Alice", 0, SEED);
+		let bob: T::AccountId = account("Bob", 0, SEED);
+		let balance = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(u32::MAX.into());
+		let bond = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(1000u32.into());
+		let vote_amount = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(5000u32.into());
+
+		frame_system::Pallet::<T>::set_block_number(One::one());
+
+		<T as pallet::Config>::NativeBalance::set_balance(&alice, balance);
+		<T as pallet::Config>::NativeBalance::set_balance(&bob, balance);
+
+		// Make sure the quorum is reached, so the posts are rewarded
+		CurrentParameters::<T>::mutate(|parameters| {
+			parameters.quorum_votes = Zero::zero();
+			parameters.quorum_voters = 0;
+		});
+
+		// Submit a bunch of posts that all end on the same block, each with a bullish vote
+		let mut post_ids: Vec<[u8; 32]> = Vec::new();
+		for i in 0..x {
+			let post: Vec<u8> = i.to_le_bytes().to_vec();
+			post_ids.push(sp_io::hashing::blake2_256(&post));
+			BullPosting::<T>::try_submit_post(RawOrigin::Signed(alice.clone()).into(), post.clone(), bond)?;
+			BullPosting::<T>::try_submit_vote(RawOrigin::Signed(bob.clone()).into(), post, vote_amount, VoteChoice::Aye)?;
+		}
+
+		let new_block_num = frame_system::Pallet::<T>::block_number() +
+		CurrentParameters::<T>::get().voting_period;
+
+		frame_system::Pallet::<T>::set_block_number(new_block_num);
+
+		#[block]
+		{
+			BullPosting::<T>::resolve_expired(new_block_num);
+		}
+
+		// assert that as many posts as the budget allows were resolved
+		let resolved = post_ids.iter()
+			.filter(|id| Posts::<T>::get(id).is_some_and(|post| post.resolved))
+			.count() as u32;
+		assert_eq!(resolved, x.min(T::MaxResolutionsPerBlock::get()));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn on_idle_end_post(
+		x: Linear<0, 1000>
+	) -> Result<(), BenchmarkError> {
+		let post: Vec<u8> = [255u8; MAX_URL].to_vec();
+		let post_id: [u8; 32] = sp_io::hashing::blake2_256(&post);
+		let alice: T::AccountId = account("Alice", 0, SEED);
+		let bob: T::AccountId = account("Bob", 0, SEED);
+		let balance = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(u32::MAX.into());
+		let bond = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(1000u32.into());
+		let vote_amount = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(5000u32.into());
+
+		frame_system::Pallet::<T>::set_block_number(One::one());
+
+		<T as pallet::Config>::NativeBalance::set_balance(&alice, balance);
+		<T as pallet::Config>::NativeBalance::set_balance(&bob, balance);
+
+		BullPosting::<T>::try_submit_post(RawOrigin::Signed(alice.clone()).into(), post.clone(), bond)?;
+
+		// Add a bunch of votes
+		for i in 0..x {
+			let acc: T::AccountId = account("filler", i, SEED);
+			<T as pallet::Config>::NativeBalance::set_balance(&acc, balance);
+			BullPosting::<T>::try_submit_vote(RawOrigin::Signed(acc).into(), post.clone(), vote_amount, VoteChoice::Aye)?;
+		}
+
+		let new_block_num = frame_system::Pallet::<T>::block_number() +
+		CurrentParameters::<T>::get().voting_period + One::one();
+
+		frame_system::Pallet::<T>::set_block_number(new_block_num);
+
+		BullPosting::<T>::try_resolve_voting(RawOrigin::Signed(bob.clone()).into(), post.clone())?;
+
+		#[block]
+		{
+			BullPosting::<T>::end_post(post_id, x)?;
+		}
+
+		assert!(!Posts::<T>::contains_key(post_id));
+		assert!(!PostsToEnd::<T>::contains_key(post_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_parameters() -> Result<(), BenchmarkError> {
+		let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let mut parameters = CurrentParameters::<T>::get();
+		parameters.voting_period += One::one();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, parameters.clone());
+
+		assert_eq!(CurrentParameters::<T>::get(), parameters);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn try_retract_vote() -> Result<(), BenchmarkError> {
+		let post: Vec<u8> = [245u8; MAX_URL].to_vec();
+		let post_id: [u8; 32] = sp_io::hashing::blake2_256(&post);
+		let alice: T::AccountId = account("Alice", 0, SEED);
+		let bob: T::AccountId = account("Bob", 0, SEED);
+		let balance = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(u32::MAX.into());
+		let bond = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(1000u32.into());
+		let vote_amount = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(5000u32.into());
+
+		frame_system::Pallet::<T>::set_block_number(One::one());
+
+		<T as pallet::Config>::NativeBalance::set_balance(&alice, balance);
+		<T as pallet::Config>::NativeBalance::set_balance(&bob, balance);
+
+		BullPosting::<T>::try_submit_post(RawOrigin::Signed(alice.clone()).into(), post.clone(), bond)?;
+
+		BullPosting::<T>::try_submit_vote(RawOrigin::Signed(bob.clone()).into(), post.clone(), vote_amount, VoteChoice::Aye)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(bob.clone()), post);
+
+		assert_last_event::<T>(Event::VoteRetracted {
+			id: post_id,
+			voter: bob.clone(),
+			amount: vote_amount,
+		}.into());
+		assert!(!Votes::<T>::contains_key(bob, post_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn try_withdraw_post() -> Result<(), BenchmarkError> {
+		let post: Vec<u8> = [240u8; MAX_URL].to_vec();
+		let post_id: [u8; 32] = sp_io::hashing::blake2_256(&post);
+		let alice: T::AccountId = account("Alice", 0, SEED);
+		let balance = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(u32::MAX.into());
+		let bond = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(1000u32.into());
+
+		frame_system::Pallet::<T>::set_block_number(One::one());
+
+		<T as pallet::Config>::NativeBalance::set_balance(&alice, balance);
+
+		BullPosting::<T>::try_submit_post(RawOrigin::Signed(alice.clone()).into(), post.clone(), bond)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(alice.clone()), post);
+
+		assert!(!Posts::<T>::contains_key(post_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn try_commit_vote() -> Result<(), BenchmarkError> {
+		let post: Vec<u8> = [235u8; MAX_URL].to_vec();
+		let post_id: [u8; 32] = sp_io::hashing::blake2_256(&post);
+		let alice: T::AccountId = account("Alice", 0, SEED);
+		let bob: T::AccountId = account("Bob", 0, SEED);
+		let balance = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(u32::MAX.into());
+		let bond = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(1000u32.into());
+		let collateral = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(5000u32.into());
+		let commitment = BullPosting::<T>::vote_commitment(&bob, &post_id, &VoteChoice::Aye, collateral, &[7u8; 32]);
+
+		CurrentParameters::<T>::mutate(|parameters| parameters.commit_reveal = true);
+		frame_system::Pallet::<T>::set_block_number(One::one());
+
+		<T as pallet::Config>::NativeBalance::set_balance(&alice, balance);
+		<T as pallet::Config>::NativeBalance::set_balance(&bob, balance);
+
+		BullPosting::<T>::try_submit_post(RawOrigin::Signed(alice.clone()).into(), post.clone(), bond)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(bob.clone()), post, commitment, collateral);
+
+		assert_last_event::<T>(Event::VoteCommitted {
+			id: post_id,
+			voter: bob.clone(),
+			collateral,
+		}.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn try_reveal_vote() -> Result<(), BenchmarkError> {
+		let post: Vec<u8> = [230u8; MAX_URL].to_vec();
+		let post_id: [u8; 32] = sp_io::hashing::blake2_256(&post);
+		let alice: T::AccountId = account("Alice", 0, SEED);
+		let bob: T::AccountId = account("Bob", 0, SEED);
+		let balance = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(u32::MAX.into());
+		let bond = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(1000u32.into());
+		let vote_amount = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(5000u32.into());
+		let salt = [7u8; 32];
+		let commitment = BullPosting::<T>::vote_commitment(&bob, &post_id, &VoteChoice::Aye, vote_amount, &salt);
+
+		CurrentParameters::<T>::mutate(|parameters| parameters.commit_reveal = true);
+		frame_system::Pallet::<T>::set_block_number(One::one());
+
+		<T as pallet::Config>::NativeBalance::set_balance(&alice, balance);
+		<T as pallet::Config>::NativeBalance::set_balance(&bob, balance);
+
+		BullPosting::<T>::try_submit_post(RawOrigin::Signed(alice.clone()).into(), post.clone(), bond)?;
+		BullPosting::<T>::try_commit_vote(RawOrigin::Signed(bob.clone()).into(), post.clone(), commitment, vote_amount)?;
+
+		// Move into the reveal period
+		let voting_until = Posts::<T>::get(post_id).expect("Post was just submitted").voting_until;
+		frame_system::Pallet::<T>::set_block_number(voting_until);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(bob.clone()), post, VoteChoice::Aye, vote_amount, salt);
+
+		assert_last_event::<T>(Event::VoteRevealed {
+			id: post_id,
+			voter: bob.clone(),
+			vote_amount,
+			vote: VoteChoice::Aye,
+		}.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn delegate(
+		x: Linear<0, 100>
+	) -> Result<(), BenchmarkError> {
+		let alice: T::AccountId = account("Alice", 0, SEED);
+		let bob: T::AccountId = account("Bob", 0, SEED);
+		let charlie: T::AccountId = account("Charlie", 0, SEED);
+		let balance = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(u32::MAX.into());
+		let bond = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(1000u32.into());
+		let vote_amount = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(5000u32.into());
+
+		frame_system::Pallet::<T>::set_block_number(One::one());
+
+		<T as pallet::Config>::NativeBalance::set_balance(&alice, balance);
+		<T as pallet::Config>::NativeBalance::set_balance(&bob, balance);
+		<T as pallet::Config>::NativeBalance::set_balance(&charlie, balance);
+
+		// Bob votes on `x` open posts, each of which has its tally updated
+		for i in 0..x.min(T::MaxVotesPerAccount::get()) {
+			let post: Vec<u8> = [i as u8; MAX_URL].to_vec();
+			BullPosting::<T>::try_submit_post(RawOrigin::Signed(alice.clone()).into(), post.clone(), bond)?;
+			BullPosting::<T>::try_submit_vote(RawOrigin::Signed(bob.clone()).into(), post, vote_amount, VoteChoice::Aye)?;
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(charlie.clone()), bob.clone(), vote_amount);
+
+		assert_eq!(DelegatedBalance::<T>::get(bob).amount, vote_amount);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn undelegate(
+		x: Linear<0, 100>
+	) -> Result<(), BenchmarkError> {
+		let alice: T::AccountId = account("Alice", 0, SEED);
+		let bob: T::AccountId = account("Bob", 0, SEED);
+		let charlie: T::AccountId = account("Charlie", 0, SEED);
+		let balance = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(u32::MAX.into());
+		let bond = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(1000u32.into());
+		let vote_amount = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(5000u32.into());
+
+		frame_system::Pallet::<T>::set_block_number(One::one());
+
+		<T as pallet::Config>::NativeBalance::set_balance(&alice, balance);
+		<T as pallet::Config>::NativeBalance::set_balance(&bob, balance);
+		<T as pallet::Config>::NativeBalance::set_balance(&charlie, balance);
+
+		BullPosting::<T>::delegate(RawOrigin::Signed(charlie.clone()).into(), bob.clone(), vote_amount)?;
+
+		// Bob votes on `x` open posts, each of which has its tally updated
+		for i in 0..x.min(T::MaxVotesPerAccount::get()) {
+			let post: Vec<u8> = [i as u8; MAX_URL].to_vec();
+			BullPosting::<T>::try_submit_post(RawOrigin::Signed(alice.clone()).into(), post.clone(), bond)?;
+			BullPosting::<T>::try_submit_vote(RawOrigin::Signed(bob.clone()).into(), post, vote_amount, VoteChoice::Aye)?;
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(charlie.clone()));
+
+		assert!(!Delegations::<T>::contains_key(charlie));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_conviction() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), Conviction::Locked6x);
+
+		assert_eq!(AccountConvictions::<T>::get(caller), Conviction::Locked6x);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unlock() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let balance = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(u32::MAX.into());
+		let vote_amount = <T as pallet::Config>::NativeBalance::minimum_balance().saturating_add(5000u32.into());
+
+		frame_system::Pallet::<T>::set_block_number(One::one());
+
+		<T as pallet::Config>::NativeBalance::set_balance(&caller, balance);
+
+		// The caller has the maximum number of conviction locks, all of which have lapsed
+		let mut expiries = BoundedVec::<_, T::MaxVotesPerAccount>::new();
+		for _ in 0..T::MaxVotesPerAccount::get() {
+			let _ = expiries.try_push((One::one(), vote_amount));
+		}
+		LockExpiries::<T>::insert(&caller, expiries);
+		BullPosting::<T>::update_vote_freeze(&caller)?;
+
+		frame_system::Pallet::<T>::set_block_number(2u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(!LockExpiries::<T>::contains_key(caller));
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(BullPosting, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -25,34 +25,58 @@ mod tests;
 mod benchmarking;
 pub mod weights;
 pub use weights::*;
+pub mod policies;
+pub use policies::*;
+pub mod migrations;
+
+/// The prefix of the offchain index keys that post inputs are stored under
+pub const OFFCHAIN_POST_PREFIX: &[u8] = b"bullposting::post";
+
+/// The offchain index key that a post's input is stored under, so nodes with offchain indexing enabled
+/// can serve the full text of a post after the transaction that submitted it is pruned
+pub fn offchain_post_key(id: &[u8; 32]) -> scale_info::prelude::vec::Vec<u8> {
+    codec::Encode::encode(&(OFFCHAIN_POST_PREFIX, id))
+}
 
 // All pallet logic is defined in its own module and must be annotated by the `pallet` attribute.
 #[frame_support::pallet]
+// The `try_state` hook expands to a `map_err` that only logs the error
+#[cfg_attr(feature = "try-runtime", allow(clippy::manual_inspect))]
 pub mod pallet {
     // Import various useful types required by all FRAME pallets.
     use super::*;
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
+    #[cfg(any(feature = "try-runtime", test))]
+    use frame_support::sp_runtime::TryRuntimeError;
 
     // Other imports
     use codec::MaxEncodedLen;
     use scale_info::prelude::{fmt::Debug, vec::Vec};
     use frame_support::{
+        migrations::MultiStepMigrator,
         traits::{
-            tokens::{fungible, Preservation, Fortitude, Precision},
+            tokens::{fungible, Preservation, Fortitude, Precision, Restriction},
             fungible::{Inspect, Mutate, MutateHold, MutateFreeze},
         },
         sp_runtime::{
-            traits::{CheckedSub, Zero},
-            Permill,
+            traits::{AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, CheckedSub, IntegerSquareRoot, One, SaturatedConversion, Saturating, Zero},
+            Perbill,
             Percent,
         },
+        storage::with_storage_layer,
         BoundedVec,
+        PalletId,
     };
 
+    /// The in-code storage version, bumped whenever the layout of the pallet's storage changes.
+    /// Migrations between versions are in the [`migrations`] module.
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
     // The `Pallet` struct serves as a placeholder to implement traits, methods and dispatchables
     // (`Call`s) in this pallet.
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// The pallet's configuration trait.
@@ -78,6 +102,12 @@ pub mod pallet {
         /// The ID type for freezes.
 		type FreezeIdentifier: Parameter + Member + MaxEncodedLen + Copy;
 
+        /// The origin allowed to update the pallet's parameters with `set_parameters`.
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        // The economic constants below are the default parameters, used until they are set at genesis
+        // or updated with `set_parameters`. See `CurrentParameters`.
+
         /// Determines which reward mechanism is used if a post is determined to be Bullish.
         /// False == FlatReward
         /// True == RewardCoefficient
@@ -92,9 +122,8 @@ pub mod pallet {
         type FlatReward: Get<BalanceOf<Self>>;
 
         /// Determines the submitter's reward if their post is determined to be Bullish, based on the size of their bond.
-        /// A value of 100 is a 1x reward. 200 will give a 2x reward
-        /// (eg. you bond 500 tokens, you will receive a 1000 token reward and end with 1500 tokens).
-        /// Values between 0 and 100 can be used as well.
+        /// A value of 100 is a 1x reward (eg. you bond 500 tokens, you will receive a 500 token reward and end with
+        /// 1000 tokens), a value of 50 is a 0.5x reward. Values higher than 100 are invalid.
         /// NOTE: This will only happen if `RewardStyle == true`
         #[pallet::constant]
         type RewardCoefficient: Get<u32>;
@@ -134,13 +163,40 @@ pub mod pallet {
         #[pallet::constant]
         type VoteMinimum: Get<BalanceOf<Self>>;
 
+        /// Determines the minimum total amount of Bullish, Bearish and Abstain votes a post needs to be rewarded or slashed.
+        /// Posts below this resolve to `NoQuorum`, releasing the bond with no reward or slash.
+        #[pallet::constant]
+        type QuorumVotes: Get<BalanceOf<Self>>;
+
+        /// Determines the minimum number of distinct voters a post needs to be rewarded or slashed.
+        /// Posts below this resolve to `NoQuorum`, releasing the bond with no reward or slash.
+        #[pallet::constant]
+        type QuorumVoters: Get<u32>;
+
+        /// Determines the fee taken from the submitter's bond when they withdraw their post with `try_withdraw_post`.
+        /// The fee goes to the pot if `PotId` is set, and is burned otherwise.
+        #[pallet::constant]
+        type WithdrawalFee: Get<BalanceOf<Self>>;
+
         /// Determines the maximum amount of accounts that can vote on a post.
-        /// This is used to bound a vector storing all of the accounts that have voted on a particular post,
-        /// so performance will slow as the value is increased (assuming the `MaxVoters` limit is actually reached on posts).
+        /// Voters are stored individually, so this doesn't affect the cost of voting, only how many calls to
+        /// `try_end_post` (or blocks of `on_idle`) it can take to unfreeze all of a post's votes.
         /// Calling `try_submit_vote` on a post that has reached the `MaxVoters` limit will fail.
         #[pallet::constant]
         type MaxVoters: Get<u32>;
 
+        /// Determines the maximum amount of posts an account can have active votes on at once.
+        /// Calling `try_submit_vote` once this limit is reached will fail until one of the votes is unfrozen or retracted.
+        #[pallet::constant]
+        type MaxVotesPerAccount: Get<u32>;
+
+        /// Determines how the tokens backing a vote are locked.
+        /// Freeze == The account's largest vote is frozen, so the same tokens can vote on several posts at once
+        /// Hold == Each vote is held separately with `HoldReason::Vote`, so tokens can only back one vote at a time
+        /// NOTE: Only change this while no votes are active, as existing votes are unlocked the same way they were locked
+        #[pallet::constant]
+        type VoteCollateral: Get<VoteCollateral>;
+
         /// Determines the amount of tokens that must be locked in order to submit a post.
         /// This is separate from the post's bond and is not involved in the reward process.
         /// This value should be sufficiently high to prevent storage bloat attacks.
@@ -159,18 +215,117 @@ pub mod pallet {
         #[pallet::constant]
         type UnfreezeLimit: Get<u32>;
 
+        /// Determines the maximum number of posts whose voting period can end on the same block.
+        /// This bounds the expiry queue stored for each block number.
+        /// Calling `try_submit_post` when the expiry queue for its `voting_until` block is full will fail.
+        #[pallet::constant]
+        type MaxExpiringPerBlock: Get<u32>;
+
+        /// Determines the maximum number of posts that are automatically resolved in `on_initialize` each block.
+        /// Due posts beyond this limit are carried over and resolved in the following blocks.
+        #[pallet::constant]
+        type MaxResolutionsPerBlock: Get<u32>;
+
+        /// Determines the maximum number of blocks of the expiry queue scanned in `on_initialize` each block.
+        /// If blocks were skipped (eg. while the chain was stalled), the rest are scanned in the following blocks.
+        #[pallet::constant]
+        type MaxExpiryBlocksScanned: Get<u32>;
+
+        /// Determines whether the voters on the winning side of a post are paid out.
+        /// If true, the amount slashed from a Bearish post's submitter (and `VoterRewardCut` of a Bullish post's reward)
+        /// is shared among the winning voters pro-rata to their vote, and paid out as their votes are unfrozen.
+        #[pallet::constant]
+        type VoterPayouts: Get<bool>;
+
+        /// Determines the share of a Bullish post's reward that goes to the winning voters instead of the submitter.
+        /// A value of 20% with a 1000 token reward will give the submitter 800 tokens and share 200 among the voters.
+        /// NOTE: This will only happen if `VoterPayouts == true`
+        #[pallet::constant]
+        type VoterRewardCut: Get<Percent>;
+
+        /// Determines whether new posts use commit-reveal voting.
+        /// If true, voters commit a hash of their vote with `try_commit_vote` during the voting period and reveal it with
+        /// `try_reveal_vote` during the `RevealPeriod` that follows, so the tallies stay hidden until the voting period ends.
+        #[pallet::constant]
+        type CommitReveal: Get<bool>;
+
+        /// Determines for how many blocks after the voting period ends commitments can be revealed.
+        /// Commit-reveal posts are resolved once this period ends.
+        /// NOTE: This will only happen if `CommitReveal == true`
+        #[pallet::constant]
+        type RevealPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Determines the share of a commitment's collateral that is forfeited if it isn't revealed in time.
+        /// The forfeited tokens go to the pot if `PotId` is set, and are burned otherwise.
+        /// NOTE: This will only happen if `CommitReveal == true`
+        #[pallet::constant]
+        type UnrevealedForfeit: Get<Percent>;
+
+        /// Determines how votes are weighted when deciding the result of new posts.
+        /// Linear == Each token counts once
+        /// Quadratic == Votes count with the square root of their amount, so large holders count for less
+        /// OneAccountOneVote == Every vote counts once, as long as it's at least `VoteMinimum`
+        #[pallet::constant]
+        type TallyMode: Get<TallyMode>;
+
+        /// Calculates the submitter's reward if their post is determined to be Bullish.
+        /// `StylePolicy` uses `RewardStyle` to pick between `FlatReward` and `RewardCoefficient`.
+        type RewardPolicy: RewardPolicy<Self>;
+
+        /// Calculates how much of the submitter's bond is slashed if their post is determined to be Bearish.
+        /// `StylePolicy` uses `SlashStyle` to pick between `FlatSlash` and `SlashCoefficient`.
+        type SlashPolicy: SlashPolicy<Self>;
+
+        /// The `PalletId` of the pot that slashed bonds are transferred into and rewards are paid out of.
+        /// If `None`, slashed bonds are burned and rewards are minted, changing the total issuance.
+        /// NOTE: The pot must hold at least the existential deposit, which the genesis config takes care of.
+        #[pallet::constant]
+        type PotId: Get<Option<PalletId>>;
+
+        /// Determines what happens when the pot can't cover a reward.
+        /// PayPartially == Only the pot's balance is paid out
+        /// MintUpToCap == The rest is minted, up to `ShortfallMintCap` per `ShortfallMintPeriod`
+        /// NOTE: This will only happen if `PotId` is set
+        #[pallet::constant]
+        type PotShortfall: Get<ShortfallBehaviour>;
+
+        /// Determines the maximum amount of tokens that can be minted to cover pot shortfalls per `ShortfallMintPeriod`.
+        #[pallet::constant]
+        type ShortfallMintCap: Get<BalanceOf<Self>>;
+
+        /// Determines the length of a `ShortfallMintCap` period, in blocks.
+        #[pallet::constant]
+        type ShortfallMintPeriod: Get<BlockNumberFor<Self>>;
     }
 
     pub type BalanceOf<T> =
         <<T as Config>::NativeBalance as fungible::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
-    /// Used for the direction of votes and results
-    #[derive(Debug, PartialEq, Clone, Encode, Decode, TypeInfo, Default, MaxEncodedLen)]
-    pub enum Direction {
+    /// What a vote is cast for
+    #[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, TypeInfo, Default, MaxEncodedLen, serde::Serialize, serde::Deserialize)]
+    pub enum VoteChoice<Balance> {
+        /// The post is Bullish
+        Aye,
+        /// The post is Bearish
+        Nay,
+        /// Neither, only counted towards the quorum
         #[default]
+        Abstain,
+        /// Split between Bullish and Bearish, the amounts adding up to the vote amount
+        Split {
+            bull: Balance,
+            bear: Balance,
+        },
+    }
+
+    /// The result of a post's voting
+    #[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, TypeInfo, MaxEncodedLen, serde::Serialize, serde::Deserialize)]
+    pub enum Outcome {
         Bullish,
         Bearish,
         Tie,
+        /// The post didn't reach the quorum
+        NoQuorum,
     }
 
     /// A reason for the pallet placing a hold on funds.
@@ -182,6 +337,9 @@ pub mod pallet {
         // Locked for storage rent, unlockable after voting ends
         #[codec(index = 1)]
         StorageRent,
+        /// Votes, if `VoteCollateral` is `Hold`
+        #[codec(index = 2)]
+        Vote,
 	}
 
     /// A reason for the pallet freezing funds.
@@ -192,6 +350,190 @@ pub mod pallet {
         Vote,
 	}
 
+    /// How the tokens backing a vote are locked
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Default)]
+    pub enum VoteCollateral {
+        /// Freeze the largest of the account's votes, the same tokens can back votes on many posts
+        #[default]
+        Freeze,
+        /// Hold each vote's amount, the same tokens can only back one vote at a time
+        Hold,
+    }
+
+    /// What to do when the pot can't cover a reward
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen)]
+    pub enum ShortfallBehaviour {
+        /// Pay out what the pot can cover
+        PayPartially,
+        /// Mint the rest, up to the per-period cap
+        MintUpToCap,
+    }
+
+    /// How much a vote's weight is multiplied, in exchange for keeping it locked after its post has ended
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Default, serde::Serialize, serde::Deserialize)]
+    pub enum Conviction {
+        /// 1x weight, unlocked when the post ends
+        #[default]
+        None,
+        /// 2x weight, locked for 1 voting period after the post's voting ends
+        Locked2x,
+        /// 3x weight, locked for 2 voting periods after the post's voting ends
+        Locked3x,
+        /// 4x weight, locked for 4 voting periods after the post's voting ends
+        Locked4x,
+        /// 5x weight, locked for 8 voting periods after the post's voting ends
+        Locked5x,
+        /// 6x weight, locked for 16 voting periods after the post's voting ends
+        Locked6x,
+    }
+
+    impl Conviction {
+        /// The multiplier applied to a vote's weight
+        pub fn multiplier(&self) -> u8 {
+            match self {
+                Conviction::None => 1,
+                Conviction::Locked2x => 2,
+                Conviction::Locked3x => 3,
+                Conviction::Locked4x => 4,
+                Conviction::Locked5x => 5,
+                Conviction::Locked6x => 6,
+            }
+        }
+
+        /// The number of voting periods a vote stays locked for after its post's voting ends
+        pub fn lock_periods(&self) -> u32 {
+            match self {
+                Conviction::None => 0,
+                Conviction::Locked2x => 1,
+                Conviction::Locked3x => 2,
+                Conviction::Locked4x => 4,
+                Conviction::Locked5x => 8,
+                Conviction::Locked6x => 16,
+            }
+        }
+
+        /// The weight of a vote of `amount` tokens
+        pub fn votes<B: From<u8> + Saturating>(&self, amount: B) -> B {
+            amount.saturating_mul(B::from(self.multiplier()))
+        }
+    }
+
+    /// How votes are weighted when deciding the result of a post
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, Default, serde::Serialize, serde::Deserialize)]
+    pub enum TallyMode {
+        /// Each token counts once
+        #[default]
+        Linear,
+        /// Votes count with the square root of their amount
+        Quadratic,
+        /// Every vote counts once, regardless of its amount
+        OneAccountOneVote,
+    }
+
+    /// The economic parameters of the pallet, updatable by `AdminOrigin`.
+    /// Each post keeps the parameters that were in effect when it was submitted.
+    #[derive(MaxEncodedLen, Debug, PartialEq, Eq, Clone, Encode, Decode, TypeInfo, serde::Serialize, serde::Deserialize)]
+    pub struct BullpostingParameters<Balance, BlockNumber> {
+        /// False == `flat_reward`, True == `reward_coefficient`
+        pub reward_style: bool,
+        /// The submitter's reward for a Bullish post, independent of their bond
+        pub flat_reward: Balance,
+        /// The submitter's reward for a Bullish post as a percentage of their bond, up to 100
+        pub reward_coefficient: u32,
+        /// False == `flat_slash`, True == `slash_coefficient`
+        pub slash_style: bool,
+        /// The amount slashed from the submitter's bond for a Bearish post
+        pub flat_slash: Balance,
+        /// The percentage of the submitter's bond slashed for a Bearish post
+        pub slash_coefficient: u8,
+        /// The number of blocks a post can be voted on for
+        pub voting_period: BlockNumber,
+        /// The minimum bond of a post
+        pub bond_minimum: Balance,
+        /// The minimum amount of tokens to vote with
+        pub vote_minimum: Balance,
+        /// The minimum total amount of Bullish, Bearish and Abstain votes for a post to be rewarded or slashed
+        pub quorum_votes: Balance,
+        /// The minimum number of voters for a post to be rewarded or slashed
+        pub quorum_voters: u32,
+        /// The fee taken from the bond of a withdrawn post
+        pub withdrawal_fee: Balance,
+        /// The amount of tokens held while a post is in storage
+        pub storage_rent: Balance,
+        /// Whether the winning voters of a post are paid out
+        pub voter_payouts: bool,
+        /// The share of a Bullish post's reward that goes to the winning voters
+        pub voter_reward_cut: Percent,
+        /// Whether votes are committed during the voting period and revealed afterwards
+        pub commit_reveal: bool,
+        /// The number of blocks after the voting period that commitments can be revealed for
+        pub reveal_period: BlockNumber,
+        /// The share of an unrevealed commitment's collateral that is forfeited
+        pub unrevealed_forfeit: Percent,
+        /// How votes are weighted when deciding the result
+        pub tally_mode: TallyMode,
+    }
+
+    pub type ParametersOf<T> = BullpostingParameters<BalanceOf<T>, BlockNumberFor<T>>;
+
+    /// A post along with its ID and number of voters, as returned by the runtime API
+    #[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, TypeInfo, serde::Serialize, serde::Deserialize)]
+    pub struct PostDetails<AccountId, Balance, BlockNumber> {
+        /// The post ID
+        pub id: [u8; 32],
+        /// The account that submitted the post
+        pub submitter: AccountId,
+        /// The submitter's bond
+        pub bond: Balance,
+        /// The total amount of Bullish votes
+        pub bull_votes: Balance,
+        /// The total amount of Bearish votes
+        pub bear_votes: Balance,
+        /// The total amount of abstaining votes
+        pub abstain_votes: Balance,
+        /// The weight of the Bullish votes under the post's tally mode
+        pub bull_weight: Balance,
+        /// The weight of the Bearish votes under the post's tally mode
+        pub bear_weight: Balance,
+        /// The block the voting ends on
+        pub voting_until: BlockNumber,
+        /// Whether the voting has been resolved
+        pub resolved: bool,
+        /// The number of accounts that voted
+        pub voter_count: u32,
+    }
+
+    /// The outcome of resolving a post's voting
+    #[derive(Debug, PartialEq, Clone, Encode, Decode, TypeInfo, serde::Serialize, serde::Deserialize)]
+    pub struct ResolutionPreview<Balance> {
+        /// The result of the voting
+        pub result: Outcome,
+        /// The submitter's reward, after the voters' cut
+        pub reward: Balance,
+        /// The amount slashed from the submitter's bond
+        pub slash: Balance,
+        /// The amount shared among the winning voters
+        pub voter_share: Balance,
+        /// The total amount of Bullish, Bearish and abstaining votes
+        pub turnout: Balance,
+        /// The number of accounts that voted
+        pub voter_count: u32,
+    }
+
+    /// The tokens shared among the winning voters of a resolved post
+    #[derive(MaxEncodedLen, Debug, PartialEq, Clone, Encode, Decode, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct PayoutPool<T: Config> {
+        /// The outcome voters must have voted for to be paid out
+        pub direction: Outcome,
+        /// The amount of tokens left to pay out
+        pub remaining: BalanceOf<T>,
+        /// The amount of tokens to share among the winning voters
+        pub total: BalanceOf<T>,
+        /// The total weight of the votes on the winning side, not counting the balance delegated to them
+        pub winning_votes: BalanceOf<T>,
+    }
+
     #[derive(MaxEncodedLen, Debug, PartialEq, Clone, Encode, Decode, TypeInfo)]
     #[scale_info(skip_type_params(T))]
     pub struct Post<T: Config> {
@@ -199,10 +541,59 @@ pub mod pallet {
         pub bond: BalanceOf<T>,
         pub bull_votes: BalanceOf<T>,
         pub bear_votes: BalanceOf<T>,
+        /// Abstaining votes only count towards the quorum
+        pub abstain_votes: BalanceOf<T>,
+        /// The Bullish and Bearish votes weighted by the post's `TallyMode`, which decide the result
+        pub bull_weight: BalanceOf<T>,
+        pub bear_weight: BalanceOf<T>,
+        /// The part of the weighted tallies from balance delegated to the voters, which isn't paid out of payout pools
+        pub bull_delegated_weight: BalanceOf<T>,
+        pub bear_delegated_weight: BalanceOf<T>,
         pub voting_until: BlockNumberFor<T>,
         pub resolved: bool,
     }
 
+    /// What a single vote adds to a post's totals
+    #[derive(Debug, PartialEq, Clone, Default)]
+    pub struct VoteTally<Balance> {
+        pub bull_votes: Balance,
+        pub bear_votes: Balance,
+        pub abstain_votes: Balance,
+        pub bull_weight: Balance,
+        pub bear_weight: Balance,
+        pub bull_delegated_weight: Balance,
+        pub bear_delegated_weight: Balance,
+    }
+
+    /// Balance delegated to an account, counted in its votes
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Encode, Decode, TypeInfo, MaxEncodedLen)]
+    pub struct Delegated<Balance> {
+        /// The total balance delegated
+        pub amount: Balance,
+        /// The sum of the square root of each delegation, which `TallyMode::Quadratic` counts instead of the total
+        /// so that pooling balances in one delegate doesn't outweigh voting with them separately
+        pub quadratic: Balance,
+    }
+
+    impl<Balance: AtLeast32BitUnsigned + Copy> Delegated<Balance> {
+        /// Adds a delegation of `amount`
+        pub fn add(&mut self, amount: Balance) {
+            self.amount.saturating_accrue(amount);
+            self.quadratic.saturating_accrue(amount.integer_sqrt());
+        }
+
+        /// Removes a delegation of `amount`
+        pub fn remove(&mut self, amount: Balance) {
+            self.amount.saturating_reduce(amount);
+            self.quadratic.saturating_reduce(amount.integer_sqrt());
+        }
+
+        /// Whether nothing is delegated
+        pub fn is_zero(&self) -> bool {
+            self.amount.is_zero()
+        }
+    }
+
     /// Stores the post ID as the key and a post struct (with the additional info such as the submitter) as the value
     #[pallet::storage]
     pub type Posts<T: Config> =
@@ -217,20 +608,177 @@ pub mod pallet {
     T::AccountId,
     Blake2_128Concat,
     [u8; 32],
-    (BalanceOf<T>, Direction),
+    (BalanceOf<T>, VoteChoice<BalanceOf<T>>),
     ValueQuery,
     >;
 
-    /// Stores the list of voters on each post ID
+    /// Stores the accounts that have voted on each post ID, including unrevealed commitments
+    #[pallet::storage]
+    pub type PostVoters<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    [u8; 32],
+    Blake2_128Concat,
+    T::AccountId,
+    (),
+    OptionQuery,
+    >;
+
+    /// Stores the commitment and collateral of each unrevealed vote on a commit-reveal post, per account and post
+    #[pallet::storage]
+    pub type Commitments<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        [u8; 32],
+        ([u8; 32], BalanceOf<T>),
+    >;
+
+    /// Stores the account each delegator delegated to and the amount delegated
+    #[pallet::storage]
+    pub type Delegations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (T::AccountId, BalanceOf<T>)>;
+
+    /// Stores the total balance delegated to each account
+    #[pallet::storage]
+    pub type DelegatedBalance<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Delegated<BalanceOf<T>>, ValueQuery>;
+
+    /// Stores the delegated balance counted in each of a delegate's votes, per account and post
+    /// This is added to the post's totals on top of the vote itself
+    #[pallet::storage]
+    pub type DelegatedVotes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        [u8; 32],
+        Delegated<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    /// Stores the amount locked by each of an account's active votes
+    /// With `VoteCollateral::Freeze` the account's freeze is the largest of them, with `VoteCollateral::Hold` each is held
+    #[pallet::storage]
+    pub type VoteLocksFor<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<([u8; 32], BalanceOf<T>), T::MaxVotesPerAccount>,
+        ValueQuery,
+    >;
+
+    /// Stores the locks of votes cast before `VoteLocksFor` that didn't fit in it when they were migrated, per account and post
+    /// These are locked the same way as `VoteLocksFor` until their vote is unfrozen, but no new locks are added
+    #[pallet::storage]
+    pub type LegacyVoteLocks<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        [u8; 32],
+        BalanceOf<T>,
+    >;
+
+    /// Stores the conviction each account votes with, applied to its new votes
+    #[pallet::storage]
+    pub type AccountConvictions<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Conviction, ValueQuery>;
+
+    /// Stores the conviction each vote was cast with, per account and post
+    #[pallet::storage]
+    pub type VoteConvictions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        [u8; 32],
+        Conviction,
+        ValueQuery,
+    >;
+
+    /// Stores the block number until which each of an account's ended conviction votes stays locked, along with the amount
+    /// These are locked the same way as `VoteLocksFor`, and unlocked with `unlock` once they lapse
     #[pallet::storage]
-    pub type Voters<T: Config> =
-        StorageMap<_, Blake2_128Concat, [u8; 32], BoundedVec<T::AccountId, T::MaxVoters>>;
+    pub type LockExpiries<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<(BlockNumberFor<T>, BalanceOf<T>), T::MaxVotesPerAccount>,
+        ValueQuery,
+    >;
 
     /// Stores the number of votes on each post ID
     #[pallet::storage]
     pub type VoteCounts<T: Config> =
         StorageMap<_, Blake2_128Concat, [u8; 32], u32>;
 
+    /// Stores the number of commitments made on each commit-reveal post ID, which count towards `MaxVoters`
+    #[pallet::storage]
+    pub type CommitmentCounts<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], u32, ValueQuery>;
+
+    /// Stores the IDs of the posts whose voting period ends at each block number
+    #[pallet::storage]
+    pub type ExpiringPosts<T: Config> =
+        StorageMap<_, Twox64Concat, BlockNumberFor<T>, BoundedVec<[u8; 32], T::MaxExpiringPerBlock>, ValueQuery>;
+
+    /// Stores the earliest block number whose expiring posts have not all been resolved yet
+    #[pallet::storage]
+    pub type ExpiryCursor<T: Config> = StorageValue<_, BlockNumberFor<T>>;
+
+    /// Stores the IDs of resolved posts that still have votes to unfreeze or storage rent to release
+    #[pallet::storage]
+    pub type PostsToEnd<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], ()>;
+
+    /// The default parameters, taken from the pallet's `Config` constants
+    #[pallet::type_value]
+    pub fn DefaultParameters<T: Config>() -> ParametersOf<T> {
+        BullpostingParameters {
+            reward_style: T::RewardStyle::get(),
+            flat_reward: T::FlatReward::get(),
+            reward_coefficient: T::RewardCoefficient::get(),
+            slash_style: T::SlashStyle::get(),
+            flat_slash: T::FlatSlash::get(),
+            slash_coefficient: T::SlashCoefficient::get(),
+            voting_period: T::VotingPeriod::get(),
+            bond_minimum: T::BondMinimum::get(),
+            vote_minimum: T::VoteMinimum::get(),
+            quorum_votes: T::QuorumVotes::get(),
+            quorum_voters: T::QuorumVoters::get(),
+            withdrawal_fee: T::WithdrawalFee::get(),
+            storage_rent: T::StorageRent::get(),
+            voter_payouts: T::VoterPayouts::get(),
+            voter_reward_cut: T::VoterRewardCut::get(),
+            commit_reveal: T::CommitReveal::get(),
+            reveal_period: T::RevealPeriod::get(),
+            unrevealed_forfeit: T::UnrevealedForfeit::get(),
+            tally_mode: T::TallyMode::get(),
+        }
+    }
+
+    /// Stores the parameters used for newly submitted posts
+    #[pallet::storage]
+    pub type CurrentParameters<T: Config> =
+        StorageValue<_, ParametersOf<T>, ValueQuery, DefaultParameters<T>>;
+
+    /// Stores the parameters each post was submitted under
+    #[pallet::storage]
+    pub type PostParameters<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], ParametersOf<T>>;
+
+    /// Stores the current `ShortfallMintPeriod` and the amount of tokens minted to cover pot shortfalls during it
+    #[pallet::storage]
+    pub type PeriodShortfallMinted<T: Config> =
+        StorageValue<_, (BlockNumberFor<T>, BalanceOf<T>), ValueQuery>;
+
+    /// Stores the payout pool of each resolved post whose winning voters are still to be paid out
+    #[pallet::storage]
+    pub type PayoutPools<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], PayoutPool<T>>;
+
     /// Events that functions in this pallet can emit.
     ///
     /// Events are a simple means of indicating to the outside world (such as dApps, chain explorers
@@ -263,8 +811,8 @@ pub mod pallet {
             voter: T::AccountId,
             /// The amount of tokens frozen for the vote.
             vote_amount: BalanceOf<T>,
-            /// Bullish or bearish vote.
-            direction: Direction,
+            /// Bullish, Bearish, Abstain or split.
+            vote: VoteChoice<BalanceOf<T>>,
         },
         /// Vote updated successfully.
         VoteUpdated {
@@ -274,8 +822,87 @@ pub mod pallet {
             voter: T::AccountId,
             /// The amount of tokens frozen for the vote.
             vote_amount: BalanceOf<T>,
-            /// Bullish or bearish vote.
-            direction: Direction,
+            /// Bullish, Bearish, Abstain or split.
+            vote: VoteChoice<BalanceOf<T>>,
+        },
+        /// Post withdrawn by its submitter before anyone voted on it.
+        PostWithdrawn {
+            /// The post ID.
+            id: [u8; 32],
+            /// The account that submitted the post.
+            submitter: T::AccountId,
+            /// The withdrawal fee taken from the bond.
+            fee: BalanceOf<T>,
+        },
+        /// Vote retracted before the voting period ended.
+        VoteRetracted {
+            /// The post ID.
+            id: [u8; 32],
+            /// The account that retracted their vote.
+            voter: T::AccountId,
+            /// The amount of tokens unfrozen.
+            amount: BalanceOf<T>,
+        },
+        /// Vote committed on a commit-reveal post.
+        VoteCommitted {
+            /// The post ID.
+            id: [u8; 32],
+            /// The account voting on the post.
+            voter: T::AccountId,
+            /// The amount of tokens locked until the commitment is revealed.
+            collateral: BalanceOf<T>,
+        },
+        /// Committed vote revealed and added to the post's totals.
+        VoteRevealed {
+            /// The post ID.
+            id: [u8; 32],
+            /// The account voting on the post.
+            voter: T::AccountId,
+            /// The amount of tokens frozen for the vote.
+            vote_amount: BalanceOf<T>,
+            /// Bullish, Bearish, Abstain or split.
+            vote: VoteChoice<BalanceOf<T>>,
+        },
+        /// Part of the collateral of a commitment that wasn't revealed in time was forfeited.
+        CommitmentForfeited {
+            /// The post ID.
+            id: [u8; 32],
+            /// The account that didn't reveal their vote.
+            voter: T::AccountId,
+            /// The amount of tokens forfeited.
+            amount: BalanceOf<T>,
+        },
+        /// Balance delegated to another account's votes.
+        Delegated {
+            /// The account delegating their balance.
+            delegator: T::AccountId,
+            /// The account whose votes the balance is added to.
+            delegate: T::AccountId,
+            /// The amount of tokens delegated and locked.
+            amount: BalanceOf<T>,
+        },
+        /// Delegation removed, unlocking the delegated balance.
+        Undelegated {
+            /// The account that delegated their balance.
+            delegator: T::AccountId,
+            /// The account the balance was delegated to.
+            delegate: T::AccountId,
+            /// The amount of tokens unlocked.
+            amount: BalanceOf<T>,
+        },
+        /// Conviction set for an account's future votes.
+        ConvictionSet {
+            /// The account setting their conviction.
+            who: T::AccountId,
+            /// The new conviction.
+            conviction: Conviction,
+        },
+        /// Lapsed conviction locks removed, unlocking their tokens.
+        Unlocked {
+            /// The account whose locks lapsed.
+            who: T::AccountId,
+            /// The total amount of the lapsed locks.
+            amount: BalanceOf<T>,
         },
         /// Vote resolved, rewarding or slashing the submitter.
         VotingResolved {
@@ -284,21 +911,85 @@ pub mod pallet {
             /// The account that submitted the post and bonded tokens.
             submitter: T::AccountId,
             /// Bullish means the submitter was rewarded, Bearish means they were slashed
-            result: Direction,
+            result: Outcome,
             rewarded: BalanceOf<T>,
             slashed: BalanceOf<T>,
+            /// The total amount of Bullish, Bearish and abstaining votes.
+            turnout: BalanceOf<T>,
+            /// The number of accounts that voted.
+            voter_count: u32,
+        },
+        /// A post couldn't be resolved automatically, and is left for `try_resolve_voting` to resolve.
+        ResolutionFailed {
+            /// The post ID.
+            id: [u8; 32],
+            /// Why resolving the post failed.
+            error: DispatchError,
         },
         VoteUnfrozen {
             id: [u8; 32],
             account: T::AccountId,
             amount: BalanceOf<T>,
         },
+        /// Tokens were set aside to be shared among the winning voters of a post.
+        PayoutPoolCreated {
+            /// The post ID.
+            id: [u8; 32],
+            /// The side the winning voters voted on.
+            direction: Outcome,
+            /// The amount of tokens to share.
+            amount: BalanceOf<T>,
+        },
+        /// The parameters used for new posts were updated.
+        ParametersUpdated {
+            /// The new parameters.
+            parameters: ParametersOf<T>,
+        },
+        /// A winning voter was paid their share of a post's payout pool.
+        VoterPaidOut {
+            /// The post ID.
+            id: [u8; 32],
+            /// The account that voted on the winning side.
+            voter: T::AccountId,
+            /// The amount of tokens paid out.
+            amount: BalanceOf<T>,
+        },
         PostPartiallyEnded {
             id: [u8; 32]
         },
         PostEnded {
             id: [u8; 32]
-        }
+        },
+        /// A resolved post couldn't be ended in the background, and is left for `try_end_post` to end.
+        EndPostFailed {
+            /// The post ID.
+            id: [u8; 32],
+            /// Why ending the post failed.
+            error: DispatchError,
+        },
+        /// Tokens taken from a submitter's bond (a slash or a withdrawal fee) were transferred into the pot.
+        SlashedToPot {
+            /// The post ID.
+            id: [u8; 32],
+            /// The amount of tokens transferred.
+            amount: BalanceOf<T>,
+        },
+        /// Tokens were minted to cover a reward the pot couldn't.
+        PotShortfallMinted {
+            /// The account that was paid.
+            who: T::AccountId,
+            /// The amount of tokens minted.
+            amount: BalanceOf<T>,
+        },
+        /// A reward was only partially paid because the pot (and the mint cap) couldn't cover it.
+        PotShortfall {
+            /// The account that was paid.
+            who: T::AccountId,
+            /// The reward that was due.
+            requested: BalanceOf<T>,
+            /// The amount actually paid.
+            paid: BalanceOf<T>,
+        },
     }
 
     /// Errors that can be returned by this pallet.
@@ -337,23 +1028,165 @@ pub mod pallet {
         VotingUnresolved,
         /// The voting period for a post has ended.
         VotingEnded,
+        /// Voting on the post has already been resolved.
+        PostAlreadyEnded,
+        /// Too many posts already end their voting period on the same block.
+        ExpiryQueueFull,
+        /// The parameters are out of bounds (eg. a slash coefficient above 100 or a voting period of 0).
+        InvalidParameters,
+        /// The amounts of a split vote don't add up to the vote amount.
+        InvalidSplit,
+        /// Only the submitter of a post can withdraw it.
+        NotSubmitter,
+        /// Posts can only be withdrawn before anyone votes on them.
+        PostHasVotes,
+        /// The account already has active votes on `MaxVotesPerAccount` posts.
+        TooManyVotes,
+        /// The post uses commit-reveal voting, so votes must be committed and revealed.
+        CommitRevealRequired,
+        /// The post doesn't use commit-reveal voting.
+        NotCommitReveal,
+        /// Commitments can only be revealed after the voting period, until the reveal period ends.
+        RevealNotOpen,
+        /// The account has no unrevealed commitment on the post.
+        CommitmentDoesNotExist,
+        /// The revealed vote doesn't match the commitment.
+        InvalidReveal,
+        /// The revealed vote is larger than the commitment's collateral.
+        RevealExceedsCollateral,
+        /// The account is already delegating, and must undelegate first.
+        AlreadyDelegating,
+        /// The account isn't delegating.
+        NotDelegating,
+        /// Accounts can't delegate to themselves.
+        CannotDelegateToSelf,
+        /// None of the account's conviction locks have lapsed yet.
+        NothingToUnlock,
+        /// The pallet's storage is being migrated, try again once the migration completes.
+        MigrationOngoing,
+        /// The account has votes or commitments on posts that haven't ended, and can't delegate until they are.
+        AlreadyVoting,
+        /// Votes can't be split on posts that count one vote per account.
+        SplitNotAllowed,
     }
 
-    #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        // Only runs during a runtime upgrade
-        #[cfg(feature = "try-runtime")]
-        fn try_state(_n: BlockNumber) -> Result<(), TryRuntimeError> {
-            // Ensure a storages were not wiped.
-            ensure!(!Posts::<T>::iter().count().is_zero(), "Posts storage is empty");
+    /// The pallet's parameters can be set at genesis, otherwise the `Config` defaults are used.
+    /// Posts and votes can also be seeded at genesis, holding and freezing the submitters' and voters' balances
+    /// the same way as submitting them would.
+    /// Balances and block numbers are given as `u128` and `u64` and saturated into the runtime's types.
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        pub parameters: Option<BullpostingParameters<u128, u64>>,
+        /// Posts as `(input, submitter, bond, voting_period)`, voting on them ends `voting_period` blocks
+        /// after genesis, or after the parameters' `voting_period` if it's `None`
+        #[allow(clippy::type_complexity)]
+        pub posts: Vec<(Vec<u8>, T::AccountId, u128, Option<u64>)>,
+        /// Votes on the posts above as `(voter, post input, amount, vote)`
+        #[allow(clippy::type_complexity)]
+        pub votes: Vec<(T::AccountId, Vec<u8>, u128, VoteChoice<u128>)>,
+        #[serde(skip)]
+        pub _config: core::marker::PhantomData<T>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            if let Some(p) = &self.parameters {
+                let parameters: ParametersOf<T> = BullpostingParameters {
+                    reward_style: p.reward_style,
+                    flat_reward: p.flat_reward.saturated_into(),
+                    reward_coefficient: p.reward_coefficient,
+                    slash_style: p.slash_style,
+                    flat_slash: p.flat_slash.saturated_into(),
+                    slash_coefficient: p.slash_coefficient,
+                    voting_period: p.voting_period.saturated_into(),
+                    bond_minimum: p.bond_minimum.saturated_into(),
+                    vote_minimum: p.vote_minimum.saturated_into(),
+                    quorum_votes: p.quorum_votes.saturated_into(),
+                    quorum_voters: p.quorum_voters,
+                    withdrawal_fee: p.withdrawal_fee.saturated_into(),
+                    storage_rent: p.storage_rent.saturated_into(),
+                    voter_payouts: p.voter_payouts,
+                    voter_reward_cut: p.voter_reward_cut,
+                    commit_reveal: p.commit_reveal,
+                    reveal_period: p.reveal_period.saturated_into(),
+                    unrevealed_forfeit: p.unrevealed_forfeit,
+                    tally_mode: p.tally_mode,
+                };
+                assert!(Pallet::<T>::validate_parameters(&parameters).is_ok(), "Invalid genesis bullposting parameters");
+                CurrentParameters::<T>::put(parameters);
+            }
+
+            // Make sure the pot exists, so that slashes below the existential deposit can be transferred into it
+            if let Some(pot) = Pallet::<T>::pot_account() {
+                let minimum = <<T as Config>::NativeBalance>::minimum_balance();
+                if <<T as Config>::NativeBalance>::balance(&pot) < minimum {
+                    assert!(
+                        <<T as Config>::NativeBalance>::mint_into(&pot, minimum).is_ok(),
+                        "Couldn't endow the bullposting pot"
+                    );
+                }
+            }
+
+            // Submit the seeded posts, with their own voting periods if they're set
+            for (input, submitter, bond, voting_period) in &self.posts {
+                let post_input: BoundedVec<u8, T::MaxInputLength> =
+                    BoundedVec::try_from(input.clone()).expect("Genesis post input is too long");
+                let mut parameters = CurrentParameters::<T>::get();
+                if let Some(voting_period) = voting_period {
+                    parameters.voting_period = (*voting_period).saturated_into();
+                }
+                assert!(
+                    Pallet::<T>::submit_post_with(submitter.clone(), post_input, (*bond).saturated_into(), parameters).is_ok(),
+                    "Invalid genesis bullposting post"
+                );
+            }
 
-            ensure!(!Votes::<T>::iter().count().is_zero(), "Votes storage is empty");
+            // Then vote on them
+            for (voter, input, amount, vote) in &self.votes {
+                let id = sp_io::hashing::blake2_256(input);
+                let vote = match vote {
+                    VoteChoice::Aye => VoteChoice::Aye,
+                    VoteChoice::Nay => VoteChoice::Nay,
+                    VoteChoice::Abstain => VoteChoice::Abstain,
+                    VoteChoice::Split { bull, bear } => VoteChoice::Split {
+                        bull: (*bull).saturated_into(),
+                        bear: (*bear).saturated_into(),
+                    },
+                };
+                assert!(
+                    Pallet::<T>::submit_vote(voter.clone(), id, (*amount).saturated_into(), vote).is_ok(),
+                    "Invalid genesis bullposting vote"
+                );
+            }
+        }
+    }
 
-            ensure!(!Voters::<T>::iter().count().is_zero(), "Voters storage is empty");
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        // Resolves the voting of posts whose voting period has ended
+        // Paused while a migration runs, the expiry cursor picks up where it left off afterwards
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            if Self::migrating() {
+                return T::DbWeight::get().reads(1);
+            }
+            Self::resolve_expired(n)
+        }
 
-            ensure!(!VoteCount::<T>::iter().count().is_zero(), "VoteCount storage is empty");
+        // Ends resolved posts using whatever weight is left over in the block
+        // Paused while a migration runs, which the executive already does by skipping `on_idle`
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            if Self::migrating() {
+                return T::DbWeight::get().reads(1);
+            }
+            Self::end_posts_idle(remaining_weight)
+        }
 
-            Ok(())
+        // Checks the pallet's storage invariants, run by `try-runtime`
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+            Self::do_try_state()
         }
 }
 
@@ -392,6 +1225,7 @@ pub mod pallet {
         /// - If post input is higher than the `MaxInputLength` set in the runtime ([`Error::InputTooLong`])
         /// - If the post has been submitted previously ([`Error::PostAlreadyExists`])
         /// - If the submitter does not have sufficient free tokens for their bond and the storage rent ([`Error::InsufficientFreeBalance`])
+        /// - If `MaxExpiringPerBlock` posts already end their voting on the same block ([`Error::ExpiryQueueFull`])
         #[pallet::call_index(0)]
         pub fn try_submit_post(
             origin: OriginFor<T>,
@@ -405,9 +1239,6 @@ pub mod pallet {
             // Convert the post input into a bounded vec to use in the actual logic, errors if too long
             let bounded: BoundedVec<u8, T::MaxInputLength> = BoundedVec::try_from(post_input).map_err(|_| Error::<T>::InputTooLong)?;
 
-            // Ensure the bond is higher than `BondMinimum`
-            ensure!(bond >= T::BondMinimum::get().into(), Error::<T>::BondTooLow);
-
             Self::submit_post(who, bounded, bond)?;
 
             Ok(())
@@ -419,25 +1250,28 @@ pub mod pallet {
         /// - `origin`: The origin calling the extrinsic
         /// - `post_input`: The caller's input (essentially a string)
         /// - `vote_amount`: The amount of tokens being used to vote by the caller
-        /// - `direction`: Whether the caller things the post being voted upon is `Bullish` or `Bearish`
+        /// - `vote`: Whether the caller thinks the post being voted upon is Bullish (`Aye`) or Bearish (`Nay`), abstains, or splits their vote
         /// 
         /// ## Errors
         /// The function will return an error under the following conditions:
         ///
         /// - If they submit nothing for the post_input ([`Error::EmptyInput`])
         /// - If the vote is below the VoteMinimum ([`Error::VoteTooLow`])
+        /// - If a split vote doesn't add up to the vote amount ([`Error::InvalidSplit`])
+        /// - If the vote is split on a `OneAccountOneVote` post ([`Error::SplitNotAllowed`])
         /// - If post input is higher than the `MaxInputLength` set in the runtime ([`Error::InputTooLong`])
         /// - If the post does not exist ([`Error::PostDoesNotExist`])
         /// - If the voting has already ended ([`Error::VotingEnded`])
         /// - If the post has already reached `MaxVoters` ([`Error::VotersMaxed`])
         /// - If they have already voted once ([`Error::AlreadyVoted`])
+        /// - If they are delegating and votes are frozen ([`Error::AlreadyDelegating`])
         /// - If the user tries to vote with more than their balance ([`Error::InsufficientFreeBalance`])
         #[pallet::call_index(1)]
         pub fn try_submit_vote(
             origin: OriginFor<T>,
             post_input: Vec<u8>,
             vote_amount: BalanceOf<T>,
-            direction: Direction,
+            vote: VoteChoice<BalanceOf<T>>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             // Ensure the post input is not empty
@@ -446,10 +1280,9 @@ pub mod pallet {
             // Convert the post input into a bounded vec to use in the actual logic, errors if too long
             let bounded: BoundedVec<u8, T::MaxInputLength> = BoundedVec::try_from(post_input).map_err(|_| Error::<T>::InputTooLong)?;
 
-            // Ensure the vote is higher than `VoteMinimum`
-            ensure!(vote_amount >= T::VoteMinimum::get().into(), Error::<T>::VoteTooLow);
+            let id = sp_io::hashing::blake2_256(&bounded);
 
-            Self::submit_vote(who, bounded, vote_amount, direction)?;
+            Self::submit_vote(who, id, vote_amount, vote)?;
 
             Ok(())
         }
@@ -461,13 +1294,15 @@ pub mod pallet {
         /// - `origin`: The origin calling the extrinsic
         /// - `post_input`: The caller's input (essentially a string)
         /// - `new_vote`: The new amount of tokens being used by the caller to update their previous vote on a particular input
-        /// - `direction`: Whether the caller things the post being voted upon is `Bullish` or `Bearish`
+        /// - `vote`: Whether the caller thinks the post being voted upon is Bullish (`Aye`) or Bearish (`Nay`), abstains, or splits their vote
         /// 
         /// ## Errors
         /// The function will return an error under the following conditions:
         ///
         /// - If they submit nothing for the post_input ([`Error::EmptyInput`])
         /// - If the vote is below the VoteMinimum ([`Error::VoteTooLow`])
+        /// - If a split vote doesn't add up to the vote amount ([`Error::InvalidSplit`])
+        /// - If the vote is split on a `OneAccountOneVote` post ([`Error::SplitNotAllowed`])
         /// - If post input is higher than the `MaxInputLength` set in the runtime ([`Error::InputTooLong`])
         /// - If the post does not exist ([`Error::PostDoesNotExist`])
         /// - If the voting has already ended ([`Error::VotingEnded`])
@@ -478,7 +1313,7 @@ pub mod pallet {
             origin: OriginFor<T>,
             post_input: Vec<u8>,
             new_vote: BalanceOf<T>,
-            direction: Direction
+            vote: VoteChoice<BalanceOf<T>>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             // Ensure the post input is not empty
@@ -487,17 +1322,17 @@ pub mod pallet {
             // Convert the post input into a bounded vec to use in the actual logic, errors if too long
             let bounded: BoundedVec<u8, T::MaxInputLength> = BoundedVec::try_from(post_input).map_err(|_| Error::<T>::InputTooLong)?;
 
-            // Ensure the vote is higher than `VoteMinimum`
-            ensure!(new_vote >= T::VoteMinimum::get().into(), Error::<T>::VoteTooLow);
+            let id = sp_io::hashing::blake2_256(&bounded);
 
-            Self::update_vote(who, bounded, new_vote, direction)?;
+            Self::update_vote(who, id, new_vote, vote)?;
             
             Ok(())
         }
 
 
         /// Resolves a post's vote, rewarding or slashing the submitter and enabling try_end_post.
-        /// Callable by anyone.
+        /// Callable by anyone. Posts are also resolved automatically in `on_initialize` once their voting period ends,
+        /// so this is only needed if the automatic resolution is running behind.
         /// 
         /// ## Parameters
         /// - `origin`: The origin calling the extrinsic
@@ -510,6 +1345,7 @@ pub mod pallet {
         /// - If post input is higher than the `MaxInputLength` set in the runtime ([`Error::InputTooLong`])
         /// - If the post does not exist ([`Error::PostDoesNotExist`])
         /// - If the vote is still in progress ([`Error::VotingStillOngoing`])
+        /// - If the voting has already been resolved ([`Error::PostAlreadyEnded`])
         #[pallet::call_index(3)]
         pub fn try_resolve_voting(
             origin: OriginFor<T>,
@@ -521,14 +1357,16 @@ pub mod pallet {
 
             // Convert the post input into a bounded vec to use in the actual logic, errors if too long
             let bounded: BoundedVec<u8, T::MaxInputLength> = BoundedVec::try_from(post_input).map_err(|_| Error::<T>::InputTooLong)?;
+            let id = sp_io::hashing::blake2_256(&bounded);
 
-            Self::resolve_voting(bounded)?;
+            Self::resolve_voting(id)?;
 
             Ok(())
         }
 
         /// Unlocks the submitter's storage rent and unfreezes all votes on that post.
-        /// Callable by anyone.
+        /// Callable by anyone. Resolved posts are also ended in the background by `on_idle` with leftover block weight,
+        /// so this is only needed to end a post sooner.
         /// 
         /// ## Parameters
         /// - `origin`: The origin calling the extrinsic
@@ -543,6 +1381,7 @@ pub mod pallet {
         /// - If the voting is unresolved ([`Error::VotingUnresolved`])
         #[pallet::call_index(4)]
         #[pallet::weight(T::WeightInfo::try_end_post(T::UnfreezeLimit::get()))]
+        #[allow(clippy::useless_conversion)]
         pub fn try_end_post(
             origin: OriginFor<T>,
             post_input: Vec<u8>,
@@ -553,52 +1392,642 @@ pub mod pallet {
 
             // Convert the post input into a bounded vec to use in the actual logic, errors if too long
             let bounded: BoundedVec<u8, T::MaxInputLength> = BoundedVec::try_from(post_input).map_err(|_| Error::<T>::InputTooLong)?;
+            let id = sp_io::hashing::blake2_256(&bounded);
 
-            let weight_used = Self::end_post(bounded)?;
+            let (unfreeze_count, ended) = Self::end_post(id, T::UnfreezeLimit::get())?;
 
-            Ok(weight_used)
+            if ended {
+                Ok(Some(T::WeightInfo::try_end_post(unfreeze_count)).into())
+            } else {
+                Ok(().into())
+            }
         }
-    }
-
 
-    impl<T: Config> Pallet<T> {
-        pub(crate) fn submit_post(
-            who: T::AccountId,
-            post_input: BoundedVec<u8, T::MaxInputLength>,
-            bond: BalanceOf<T>
+        /// Submits a vote on whether a particular post is bullish or bearish, addressing the post by its ID.
+        /// Behaves the same as `try_submit_vote` without having to resend the post's input.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `post_id`: The post's ID (the blake2-256 hash of its input)
+        /// - `vote_amount`: The amount of tokens being used to vote by the caller
+        /// - `vote`: Whether the caller thinks the post being voted upon is Bullish (`Aye`) or Bearish (`Nay`), abstains, or splits their vote
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If the vote is below the VoteMinimum ([`Error::VoteTooLow`])
+        /// - If a split vote doesn't add up to the vote amount ([`Error::InvalidSplit`])
+        /// - If the vote is split on a `OneAccountOneVote` post ([`Error::SplitNotAllowed`])
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the voting has already ended ([`Error::VotingEnded`])
+        /// - If the post has already reached `MaxVoters` ([`Error::VotersMaxed`])
+        /// - If they have already voted once ([`Error::AlreadyVoted`])
+        /// - If they are delegating and votes are frozen ([`Error::AlreadyDelegating`])
+        /// - If the user tries to vote with more than their balance ([`Error::InsufficientFreeBalance`])
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::try_submit_vote())]
+        pub fn try_submit_vote_by_id(
+            origin: OriginFor<T>,
+            post_id: [u8; 32],
+            vote_amount: BalanceOf<T>,
+            vote: VoteChoice<BalanceOf<T>>,
         ) -> DispatchResult {
-            let id = sp_io::hashing::blake2_256(&post_input);
+            let who = ensure_signed(origin)?;
 
-            // Checks if the post exists
-            ensure!(!Posts::<T>::contains_key(&id), Error::<T>::PostAlreadyExists);
+            Self::submit_vote(who, post_id, vote_amount, vote)?;
 
-            let storage_rent = T::StorageRent::get();
+            Ok(())
+        }
+
+        /// Updates an account's vote and freeze accordingly, addressing the post by its ID.
+        /// Behaves the same as `try_update_vote` without having to resend the post's input.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `post_id`: The post's ID (the blake2-256 hash of its input)
+        /// - `new_vote`: The new amount of tokens being used by the caller to update their previous vote on a particular post
+        /// - `vote`: Whether the caller thinks the post being voted upon is Bullish (`Aye`) or Bearish (`Nay`), abstains, or splits their vote
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If the vote is below the VoteMinimum ([`Error::VoteTooLow`])
+        /// - If a split vote doesn't add up to the vote amount ([`Error::InvalidSplit`])
+        /// - If the vote is split on a `OneAccountOneVote` post ([`Error::SplitNotAllowed`])
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the voting has already ended ([`Error::VotingEnded`])
+        /// - If this particular vote doesn't exist (['Error::VoteDoesNotExist'])
+        /// - If the user does not have enough balance for their new vote ([`Error::InsufficientFreeBalance`])
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::try_update_vote())]
+        pub fn try_update_vote_by_id(
+            origin: OriginFor<T>,
+            post_id: [u8; 32],
+            new_vote: BalanceOf<T>,
+            vote: VoteChoice<BalanceOf<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::update_vote(who, post_id, new_vote, vote)?;
+
+            Ok(())
+        }
+
+        /// Resolves a post's vote, addressing the post by its ID.
+        /// Behaves the same as `try_resolve_voting` without having to resend the post's input.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `post_id`: The post's ID (the blake2-256 hash of its input)
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the vote is still in progress ([`Error::VotingStillOngoing`])
+        /// - If the voting has already been resolved ([`Error::PostAlreadyEnded`])
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::try_resolve_voting())]
+        pub fn try_resolve_voting_by_id(
+            origin: OriginFor<T>,
+            post_id: [u8; 32],
+        ) -> DispatchResult {
+            let _who = ensure_signed(origin)?;
+
+            Self::resolve_voting(post_id)?;
+
+            Ok(())
+        }
+
+        /// Unlocks the submitter's storage rent and unfreezes all votes on that post, addressing the post by its ID.
+        /// Behaves the same as `try_end_post` without having to resend the post's input.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `post_id`: The post's ID (the blake2-256 hash of its input)
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the voting is unresolved ([`Error::VotingUnresolved`])
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::try_end_post(T::UnfreezeLimit::get()))]
+        #[allow(clippy::useless_conversion)]
+        pub fn try_end_post_by_id(
+            origin: OriginFor<T>,
+            post_id: [u8; 32],
+        ) -> DispatchResultWithPostInfo {
+            let _who = ensure_signed(origin)?;
+
+            let (unfreeze_count, ended) = Self::end_post(post_id, T::UnfreezeLimit::get())?;
+
+            if ended {
+                Ok(Some(T::WeightInfo::try_end_post(unfreeze_count)).into())
+            } else {
+                Ok(().into())
+            }
+        }
+
+        /// Updates the parameters used for newly submitted posts.
+        /// Posts that were already submitted keep the parameters they were submitted under.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic, must be `AdminOrigin`
+        /// - `parameters`: The new parameters
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If the origin is not `AdminOrigin` ([`DispatchError::BadOrigin`])
+        /// - If the parameters are out of bounds ([`Error::InvalidParameters`])
+        #[pallet::call_index(9)]
+        pub fn set_parameters(
+            origin: OriginFor<T>,
+            parameters: ParametersOf<T>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            Self::validate_parameters(&parameters)?;
+
+            CurrentParameters::<T>::put(&parameters);
+
+            // Emit an event.
+            Self::deposit_event(Event::ParametersUpdated {
+                parameters,
+            });
+
+            Ok(())
+        }
+
+        /// Retracts an account's vote and unfreezes it, freeing up their voter slot. Only possible before the voting period ends.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `post_input`: The caller's input (essentially a string)
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If they submit nothing for the post_input ([`Error::EmptyInput`])
+        /// - If post input is higher than the `MaxInputLength` set in the runtime ([`Error::InputTooLong`])
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the voting has already ended ([`Error::VotingEnded`])
+        /// - If this particular vote doesn't exist ([`Error::VoteDoesNotExist`])
+        #[pallet::call_index(10)]
+        pub fn try_retract_vote(
+            origin: OriginFor<T>,
+            post_input: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            // Ensure the post input is not empty
+            ensure!(!post_input.is_empty(), Error::<T>::EmptyInput);
+
+            // Convert the post input into a bounded vec to use in the actual logic, errors if too long
+            let bounded: BoundedVec<u8, T::MaxInputLength> = BoundedVec::try_from(post_input).map_err(|_| Error::<T>::InputTooLong)?;
+
+            let id = sp_io::hashing::blake2_256(&bounded);
+
+            Self::retract_vote(who, id)?;
+
+            Ok(())
+        }
+
+        /// Retracts an account's vote, addressing the post by its ID.
+        /// Behaves the same as `try_retract_vote` without having to resend the post's input.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `post_id`: The post's ID (the blake2-256 hash of its input)
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the voting has already ended ([`Error::VotingEnded`])
+        /// - If this particular vote doesn't exist ([`Error::VoteDoesNotExist`])
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::try_retract_vote())]
+        pub fn try_retract_vote_by_id(
+            origin: OriginFor<T>,
+            post_id: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::retract_vote(who, post_id)?;
+
+            Ok(())
+        }
+
+        /// Withdraws a post before anyone has voted on it, releasing the bond (minus the withdrawal fee) and the storage rent.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic, must be the post's submitter
+        /// - `post_input`: The caller's input (essentially a string)
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If they submit nothing for the post_input ([`Error::EmptyInput`])
+        /// - If post input is higher than the `MaxInputLength` set in the runtime ([`Error::InputTooLong`])
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the caller is not the post's submitter ([`Error::NotSubmitter`])
+        /// - If the voting was already resolved ([`Error::PostAlreadyEnded`])
+        /// - If anyone has voted on the post ([`Error::PostHasVotes`])
+        #[pallet::call_index(12)]
+        pub fn try_withdraw_post(
+            origin: OriginFor<T>,
+            post_input: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            // Ensure the post input is not empty
+            ensure!(!post_input.is_empty(), Error::<T>::EmptyInput);
+
+            // Convert the post input into a bounded vec to use in the actual logic, errors if too long
+            let bounded: BoundedVec<u8, T::MaxInputLength> = BoundedVec::try_from(post_input).map_err(|_| Error::<T>::InputTooLong)?;
+
+            let id = sp_io::hashing::blake2_256(&bounded);
+
+            Self::withdraw_post(who, id)?;
+
+            Ok(())
+        }
+
+        /// Withdraws a post, addressing it by its ID.
+        /// Behaves the same as `try_withdraw_post` without having to resend the post's input.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic, must be the post's submitter
+        /// - `post_id`: The post's ID (the blake2-256 hash of its input)
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the caller is not the post's submitter ([`Error::NotSubmitter`])
+        /// - If the voting was already resolved ([`Error::PostAlreadyEnded`])
+        /// - If anyone has voted on the post ([`Error::PostHasVotes`])
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::try_withdraw_post())]
+        pub fn try_withdraw_post_by_id(
+            origin: OriginFor<T>,
+            post_id: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::withdraw_post(who, post_id)?;
+
+            Ok(())
+        }
+
+        /// Commits a hidden vote on a commit-reveal post, locking `collateral` until it's revealed.
+        /// The commitment is `Pallet::vote_commitment(who, post_id, vote, vote_amount, salt)`, the blake2-256 hash of the encoded vote.
+        /// Binding the account and post keeps a commitment from being copied by another account or onto another post.
+        /// Only possible before the post's voting period ends.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `post_input`: The caller's input (essentially a string)
+        /// - `commitment`: The hash of the account, post ID, vote's choice, amount and a secret salt
+        /// - `collateral`: The amount of tokens to lock, which the revealed vote can't exceed
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If they submit nothing for the post_input ([`Error::EmptyInput`])
+        /// - If post input is higher than the `MaxInputLength` set in the runtime ([`Error::InputTooLong`])
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the post doesn't use commit-reveal voting ([`Error::NotCommitReveal`])
+        /// - If the collateral is below the post's `VoteMinimum` ([`Error::VoteTooLow`])
+        /// - If the voting has already ended ([`Error::VotingEnded`])
+        /// - If the post has reached `MaxVoters` ([`Error::VotersMaxed`])
+        /// - If the account already committed or voted on this post ([`Error::AlreadyVoted`])
+        /// - If the account is delegating and votes are frozen ([`Error::AlreadyDelegating`])
+        /// - If the account does not have enough balance ([`Error::InsufficientFreeBalance`])
+        /// - If the account already has votes on `MaxVotesPerAccount` posts ([`Error::TooManyVotes`])
+        #[pallet::call_index(14)]
+        pub fn try_commit_vote(
+            origin: OriginFor<T>,
+            post_input: Vec<u8>,
+            commitment: [u8; 32],
+            collateral: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            // Ensure the post input is not empty
+            ensure!(!post_input.is_empty(), Error::<T>::EmptyInput);
+
+            // Convert the post input into a bounded vec to use in the actual logic, errors if too long
+            let bounded: BoundedVec<u8, T::MaxInputLength> = BoundedVec::try_from(post_input).map_err(|_| Error::<T>::InputTooLong)?;
+
+            let id = sp_io::hashing::blake2_256(&bounded);
+
+            Self::commit_vote(who, id, commitment, collateral)?;
+
+            Ok(())
+        }
+
+        /// Commits a hidden vote, addressing the post by its ID.
+        /// Behaves the same as `try_commit_vote` without having to resend the post's input.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `post_id`: The post's ID (the blake2-256 hash of its input)
+        /// - `commitment`: The hash of the account, post ID, vote's choice, amount and a secret salt
+        /// - `collateral`: The amount of tokens to lock, which the revealed vote can't exceed
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the post doesn't use commit-reveal voting ([`Error::NotCommitReveal`])
+        /// - If the collateral is below the post's `VoteMinimum` ([`Error::VoteTooLow`])
+        /// - If the voting has already ended ([`Error::VotingEnded`])
+        /// - If the post has reached `MaxVoters` ([`Error::VotersMaxed`])
+        /// - If the account already committed or voted on this post ([`Error::AlreadyVoted`])
+        /// - If the account is delegating and votes are frozen ([`Error::AlreadyDelegating`])
+        /// - If the account does not have enough balance ([`Error::InsufficientFreeBalance`])
+        /// - If the account already has votes on `MaxVotesPerAccount` posts ([`Error::TooManyVotes`])
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::try_commit_vote())]
+        pub fn try_commit_vote_by_id(
+            origin: OriginFor<T>,
+            post_id: [u8; 32],
+            commitment: [u8; 32],
+            collateral: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::commit_vote(who, post_id, commitment, collateral)?;
+
+            Ok(())
+        }
+
+        /// Reveals a committed vote, adding it to the post's totals and only keeping the revealed amount locked.
+        /// Only possible after the post's voting period ends, until its reveal period ends.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `post_input`: The caller's input (essentially a string)
+        /// - `vote`: The committed vote
+        /// - `vote_amount`: The committed amount
+        /// - `salt`: The committed salt
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If they submit nothing for the post_input ([`Error::EmptyInput`])
+        /// - If post input is higher than the `MaxInputLength` set in the runtime ([`Error::InputTooLong`])
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the post doesn't use commit-reveal voting ([`Error::NotCommitReveal`])
+        /// - If it's not the post's reveal period ([`Error::RevealNotOpen`])
+        /// - If the account has no commitment on this post ([`Error::CommitmentDoesNotExist`])
+        /// - If the vote doesn't match the commitment ([`Error::InvalidReveal`])
+        /// - If a split vote doesn't add up to the vote amount ([`Error::InvalidSplit`])
+        /// - If the vote is split on a `OneAccountOneVote` post ([`Error::SplitNotAllowed`])
+        /// - If the vote is below the post's `VoteMinimum` ([`Error::VoteTooLow`])
+        /// - If the vote is larger than the collateral ([`Error::RevealExceedsCollateral`])
+        #[pallet::call_index(16)]
+        pub fn try_reveal_vote(
+            origin: OriginFor<T>,
+            post_input: Vec<u8>,
+            vote: VoteChoice<BalanceOf<T>>,
+            vote_amount: BalanceOf<T>,
+            salt: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            // Ensure the post input is not empty
+            ensure!(!post_input.is_empty(), Error::<T>::EmptyInput);
+
+            // Convert the post input into a bounded vec to use in the actual logic, errors if too long
+            let bounded: BoundedVec<u8, T::MaxInputLength> = BoundedVec::try_from(post_input).map_err(|_| Error::<T>::InputTooLong)?;
+
+            let id = sp_io::hashing::blake2_256(&bounded);
+
+            Self::reveal_vote(who, id, vote, vote_amount, salt)?;
+
+            Ok(())
+        }
+
+        /// Reveals a committed vote, addressing the post by its ID.
+        /// Behaves the same as `try_reveal_vote` without having to resend the post's input.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `post_id`: The post's ID (the blake2-256 hash of its input)
+        /// - `vote`: The committed vote
+        /// - `vote_amount`: The committed amount
+        /// - `salt`: The committed salt
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If the post does not exist ([`Error::PostDoesNotExist`])
+        /// - If the post doesn't use commit-reveal voting ([`Error::NotCommitReveal`])
+        /// - If it's not the post's reveal period ([`Error::RevealNotOpen`])
+        /// - If the account has no commitment on this post ([`Error::CommitmentDoesNotExist`])
+        /// - If the vote doesn't match the commitment ([`Error::InvalidReveal`])
+        /// - If a split vote doesn't add up to the vote amount ([`Error::InvalidSplit`])
+        /// - If the vote is split on a `OneAccountOneVote` post ([`Error::SplitNotAllowed`])
+        /// - If the vote is below the post's `VoteMinimum` ([`Error::VoteTooLow`])
+        /// - If the vote is larger than the collateral ([`Error::RevealExceedsCollateral`])
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::try_reveal_vote())]
+        pub fn try_reveal_vote_by_id(
+            origin: OriginFor<T>,
+            post_id: [u8; 32],
+            vote: VoteChoice<BalanceOf<T>>,
+            vote_amount: BalanceOf<T>,
+            salt: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::reveal_vote(who, post_id, vote, vote_amount, salt)?;
+
+            Ok(())
+        }
+
+        /// Delegates an amount of the caller's balance to another account, locking it like a vote.
+        /// The amount is added to the delegate's vote on every post they vote on while the delegation is active,
+        /// including their votes on posts that are still open for voting.
+        /// With `VoteCollateral::Freeze`, accounts can't vote while delegating (or delegate while voting),
+        /// so the same frozen tokens aren't counted twice.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `delegate`: The account whose votes the balance is added to
+        /// - `amount`: The amount of tokens to delegate
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If the caller delegates to themselves ([`Error::CannotDelegateToSelf`])
+        /// - If the caller is already delegating ([`Error::AlreadyDelegating`])
+        /// - If votes are frozen and the caller has votes on posts that haven't ended ([`Error::AlreadyVoting`])
+        /// - If the amount is below the current `VoteMinimum` ([`Error::VoteTooLow`])
+        /// - If the caller does not have enough balance ([`Error::InsufficientFreeBalance`])
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::delegate(T::MaxVotesPerAccount::get()))]
+        pub fn delegate(
+            origin: OriginFor<T>,
+            delegate: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::delegate_balance(who, delegate, amount)?;
+
+            Ok(())
+        }
+
+        /// Removes the caller's delegation, unlocking the delegated balance.
+        /// The balance is removed from the delegate's votes on posts that are still open for voting,
+        /// and stays counted on posts whose voting period has ended.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If the caller isn't delegating ([`Error::NotDelegating`])
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::undelegate(T::MaxVotesPerAccount::get()))]
+        pub fn undelegate(
+            origin: OriginFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::undelegate_balance(who)?;
+
+            Ok(())
+        }
+
+        /// Sets the conviction the caller's future votes are cast with.
+        /// A conviction multiplies the weight of each vote in the post's totals, and keeps the vote locked
+        /// for a number of voting periods after the post ends. Votes already cast keep their conviction.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        /// - `conviction`: The conviction to vote with
+        #[pallet::call_index(20)]
+        pub fn set_conviction(
+            origin: OriginFor<T>,
+            conviction: Conviction,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            if conviction == Conviction::None {
+                AccountConvictions::<T>::remove(&who);
+            } else {
+                AccountConvictions::<T>::insert(&who, conviction);
+            }
+
+            // Emit an event.
+            Self::deposit_event(Event::ConvictionSet {
+                who,
+                conviction,
+            });
+
+            Ok(())
+        }
+
+        /// Removes the caller's conviction locks that have lapsed, unlocking their tokens.
+        ///
+        /// ## Parameters
+        /// - `origin`: The origin calling the extrinsic
+        ///
+        /// ## Errors
+        /// The function will return an error under the following conditions:
+        ///
+        /// - If none of the caller's conviction locks have lapsed ([`Error::NothingToUnlock`])
+        #[pallet::call_index(21)]
+        pub fn unlock(
+            origin: OriginFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::unlock_expired(who)?;
+
+            Ok(())
+        }
+    }
+
+
+    impl<T: Config> Pallet<T> {
+        pub(crate) fn submit_post(
+            who: T::AccountId,
+            post_input: BoundedVec<u8, T::MaxInputLength>,
+            bond: BalanceOf<T>
+        ) -> DispatchResult {
+            Self::submit_post_with(who, post_input, bond, CurrentParameters::<T>::get())
+        }
+
+        // Submits a post under the given parameters, which are kept for the rest of its lifetime
+        pub(crate) fn submit_post_with(
+            who: T::AccountId,
+            post_input: BoundedVec<u8, T::MaxInputLength>,
+            bond: BalanceOf<T>,
+            parameters: ParametersOf<T>,
+        ) -> DispatchResult {
+            let id = sp_io::hashing::blake2_256(&post_input);
+
+            // Posts can't be used while their storage is being migrated
+            ensure!(!Self::migrating(), Error::<T>::MigrationOngoing);
+
+            // Checks if the post exists
+            ensure!(!Posts::<T>::contains_key(id), Error::<T>::PostAlreadyExists);
+
+            // Ensure the bond is higher than `BondMinimum`
+            ensure!(bond >= parameters.bond_minimum, Error::<T>::BondTooLow);
+
+            let storage_rent = parameters.storage_rent;
 
             // Checks if they have enough balance available to be bonded
             let reduc_bal = <<T as Config>::NativeBalance>::
             reducible_balance(&who, Preservation::Preserve, Fortitude::Polite);
             reduc_bal.checked_sub(&bond).ok_or(Error::<T>::InsufficientFreeBalance)?;
-            reduc_bal.checked_sub(&storage_rent.into()).ok_or(Error::<T>::InsufficientFreeBalance)?;
+            reduc_bal.checked_sub(&storage_rent).ok_or(Error::<T>::InsufficientFreeBalance)?;
 
             // Bonds the submitter's balance
             <<T as Config>::NativeBalance>::hold(&HoldReason::PostBond.into(), &who, bond)?;
 
             // Holds the storage rent
-            <<T as Config>::NativeBalance>::hold(&HoldReason::StorageRent.into(), &who, storage_rent.into())?;
+            <<T as Config>::NativeBalance>::hold(&HoldReason::StorageRent.into(), &who, storage_rent)?;
 
             let voting_until = frame_system::Pallet::<T>::block_number() +
-            T::VotingPeriod::get();
+            parameters.voting_period;
+
+            // Queue the post to be resolved automatically once its voting (and reveal) period ends
+            let resolves_at = Self::resolves_at(voting_until, &parameters);
+            ExpiringPosts::<T>::try_mutate(resolves_at, |queue| queue.try_push(id))
+                .map_err(|_| Error::<T>::ExpiryQueueFull)?;
+            // The cursor stays on the earliest queued block, as posts can have shorter voting periods than the ones before them
+            ExpiryCursor::<T>::mutate(|cursor| match cursor {
+                Some(cursor) if *cursor <= resolves_at => {},
+                _ => *cursor = Some(resolves_at),
+            });
 
             // Stores the submitter and bond info
-            Posts::<T>::insert(&id, Post {
+            Posts::<T>::insert(id, Post {
                 submitter: who.clone(),
                 bond,
                 bull_votes: Zero::zero(),
                 bear_votes: Zero::zero(),
+                abstain_votes: Zero::zero(),
+                bull_weight: Zero::zero(),
+                bear_weight: Zero::zero(),
+                bull_delegated_weight: Zero::zero(),
+                bear_delegated_weight: Zero::zero(),
                 voting_until,
                 resolved: false,
             });
 
+            // Keep the post's parameters for the rest of its lifetime
+            PostParameters::<T>::insert(id, parameters);
+
+            // Keep the full input in the offchain DB, as only its hash is stored on-chain
+            sp_io::offchain_index::set(&offchain_post_key(&id), &post_input);
+
             // Emit an event.
             Self::deposit_event(Event::PostSubmitted {
                 id,
@@ -611,15 +2040,27 @@ pub mod pallet {
 
         pub(crate) fn submit_vote(
             who: T::AccountId,
-            post_input: BoundedVec<u8, T::MaxInputLength>,
+            id: [u8; 32],
             vote_amount: BalanceOf<T>,
-            direction: Direction,
+            vote: VoteChoice<BalanceOf<T>>,
         ) -> DispatchResult {
-            let id = sp_io::hashing::blake2_256(&post_input);
+            // Posts can't be used while their storage is being migrated
+            ensure!(!Self::migrating(), Error::<T>::MigrationOngoing);
 
             // Error if the post does not exist.
-            ensure!(Posts::<T>::contains_key(&id), Error::<T>::PostDoesNotExist);
-            let post_struct = Posts::<T>::get(&id).expect("Already checked that it exists");
+            ensure!(Posts::<T>::contains_key(id), Error::<T>::PostDoesNotExist);
+            let post_struct = Posts::<T>::get(id).expect("Already checked that it exists");
+
+            let parameters = Self::post_parameters(&id);
+
+            // Commit-reveal posts are voted on with `try_commit_vote`
+            ensure!(!parameters.commit_reveal, Error::<T>::CommitRevealRequired);
+
+            // Ensure the vote is higher than the post's `VoteMinimum`
+            ensure!(vote_amount >= parameters.vote_minimum, Error::<T>::VoteTooLow);
+
+            // The amounts of a split vote must add up to the vote
+            Self::ensure_valid_split(&vote, vote_amount, parameters.tally_mode)?;
             
             // Check if voting is still open for that post
             // If current block number is greater than or equal to the ending period of the post's voting, error.
@@ -631,30 +2072,24 @@ pub mod pallet {
             }
 
             // Check if they have already voted
-            ensure!(!Votes::<T>::contains_key(&who, &id), Error::<T>::AlreadyVoted);
+            ensure!(!Votes::<T>::contains_key(&who, id), Error::<T>::AlreadyVoted);
+
+            // With `VoteCollateral::Freeze` the delegated tokens would also back the account's own vote
+            if T::VoteCollateral::get() == VoteCollateral::Freeze {
+                ensure!(!Delegations::<T>::contains_key(&who), Error::<T>::AlreadyDelegating);
+            }
 
             // Check if they have enough balance for the freeze
             ensure!(vote_amount < <<T as Config>::NativeBalance>::total_balance(&who), Error::<T>::InsufficientFreeBalance);
 
-            // Extend_freeze
-            <<T as Config>::NativeBalance>::extend_freeze(&FreezeReason::Vote.into(), &who, vote_amount)?;
+            // Lock the vote, freezing the largest of the account's votes
+            Self::set_vote_lock(&who, id, vote_amount)?;
 
             // Store vote for account and post
-            Votes::<T>::insert(&who, &id, (vote_amount, &direction));
+            Votes::<T>::insert(&who, id, (vote_amount, &vote));
 
-            // Update the list of voters for this post
-            match Voters::<T>::get(id) {
-                None => {
-                    let mut v: BoundedVec<T::AccountId, T::MaxVoters> = BoundedVec::new();
-                    let _ = v.try_push(who.clone());
-                    Voters::<T>::insert(&id, v)
-                },
-                Some(mut v) => {
-                    // Will never error as we already checked regarding the number of voters (vector length) to ensure space
-                    let _ = v.try_push(who.clone());
-                    Voters::<T>::insert(&id, v)
-                },
-            };
+            // Add them to the voters on this post
+            PostVoters::<T>::insert(id, &who, ());
 
             // Update the number of voters for this post
             match VoteCounts::<T>::get(id) {
@@ -662,33 +2097,26 @@ pub mod pallet {
                 Some(x) => { VoteCounts::<T>::insert(id, x + 1) },
             }
 
-            // Stores vote info/updates post struct according to vote direction
-            let updated_post_struct = match direction {
-                Direction::Bullish => {
-                    Post {
-                        bull_votes: post_struct.bull_votes + vote_amount,
-                        ..post_struct
-                    }
-                },
-                Direction::Bearish => {
-                    Post {
-                        bear_votes: post_struct.bear_votes + vote_amount,
-                        ..post_struct
-                    }
-                },
-                Direction::Tie => {
-                    post_struct
-                }
-            };
+            // The vote counts with the account's conviction and the balance delegated to the voter
+            let conviction = Self::set_vote_conviction(&who, id, &vote);
+            let delegated = DelegatedBalance::<T>::get(&who);
+            if !delegated.is_zero() {
+                DelegatedVotes::<T>::insert(&who, id, delegated);
+            }
 
-            Posts::<T>::insert(&id, updated_post_struct);
+            // Updates post struct according to the vote
+            let tally = Self::vote_tally(&vote, vote_amount, delegated, conviction, parameters.tally_mode);
+            let mut updated_post_struct = post_struct;
+            Self::add_to_tally(&mut updated_post_struct, &tally);
+
+            Posts::<T>::insert(id, updated_post_struct);
 
             // Emit an event.
             Self::deposit_event(Event::VoteSubmitted {
                 id,
                 voter: who,
                 vote_amount,
-                direction,
+                vote,
             });
 
             Ok(())
@@ -696,135 +2124,621 @@ pub mod pallet {
 
         pub(crate) fn update_vote(
             who: T::AccountId,
-            post_input: BoundedVec<u8, T::MaxInputLength>,
+            id: [u8; 32],
             new_vote: BalanceOf<T>,
-            direction: Direction
+            vote: VoteChoice<BalanceOf<T>>,
         ) -> DispatchResult {
-            let id = sp_io::hashing::blake2_256(&post_input);
+            // Posts can't be used while their storage is being migrated
+            ensure!(!Self::migrating(), Error::<T>::MigrationOngoing);
 
             // Error if the post does not exist.
-            ensure!(Posts::<T>::contains_key(&id), Error::<T>::PostDoesNotExist);
-            let post_struct = Posts::<T>::get(&id).expect("Already checked that it exists");
+            ensure!(Posts::<T>::contains_key(id), Error::<T>::PostDoesNotExist);
+            let post_struct = Posts::<T>::get(id).expect("Already checked that it exists");
+
+            let parameters = Self::post_parameters(&id);
+
+            // Revealed votes can't be changed
+            ensure!(!parameters.commit_reveal, Error::<T>::CommitRevealRequired);
+
+            // Ensure the vote is higher than the post's `VoteMinimum`
+            ensure!(new_vote >= parameters.vote_minimum, Error::<T>::VoteTooLow);
+
+            // The amounts of a split vote must add up to the vote
+            Self::ensure_valid_split(&vote, new_vote, parameters.tally_mode)?;
 
             // Check if voting is still open for that post
             // If current block number is greater than or equal to the ending period of the post's voting, error.
             ensure!(frame_system::Pallet::<T>::block_number() < post_struct.voting_until, Error::<T>::VotingEnded);
 
             // Error if this particular vote no longer exists or never existed.
-            ensure!(Votes::<T>::contains_key(&who, &id), Error::<T>::VoteDoesNotExist);
+            ensure!(Votes::<T>::contains_key(&who, id), Error::<T>::VoteDoesNotExist);
 
             // Error if they do not have enough balance for the freeze
             ensure!(new_vote < <<T as Config>::NativeBalance>::total_balance(&who), Error::<T>::InsufficientFreeBalance);
 
-            let (previous_amount, previous_direction) = Votes::<T>::take(&who, &id);
+            let (previous_amount, previous_vote) = Votes::<T>::take(&who, id);
 
-            // Extend_freeze
-            <<T as Config>::NativeBalance>::extend_freeze(&FreezeReason::Vote.into(), &who, new_vote)?;
+            // Update the vote's lock, freezing the largest of the account's votes
+            Self::set_vote_lock(&who, id, new_vote)?;
 
             // Store vote
-            Votes::<T>::insert(&who, &id, (new_vote, &direction));
-
-            // Updates post struct's vote totals according to vote amount and direction
-            // Removes previous directional vote and adds new vote
-            let updated_post_struct = match direction {
-                Direction::Bullish => {
-                    if previous_direction == Direction::Bullish {
-                        Post {
-                            bull_votes: post_struct.bull_votes - previous_amount + new_vote,
-                            ..post_struct
-                        }
-                    } else {
-                        Post {
-                            bull_votes: post_struct.bull_votes + new_vote,
-                            bear_votes: post_struct.bear_votes - previous_amount,
-                            ..post_struct
-                        }
-                    }
+            Votes::<T>::insert(&who, id, (new_vote, &vote));
+
+            // Updates post struct's vote totals according to vote amount and choice
+            // Removes previous vote and adds new vote, along with the balance delegated to the voter
+            // The new vote is cast with the account's current conviction
+            let previous_conviction = VoteConvictions::<T>::take(&who, id);
+            let previous_delegated = DelegatedVotes::<T>::take(&who, id);
+            let conviction = Self::set_vote_conviction(&who, id, &vote);
+            let delegated = DelegatedBalance::<T>::get(&who);
+            if !delegated.is_zero() {
+                DelegatedVotes::<T>::insert(&who, id, delegated);
+            }
+
+            let previous_tally = Self::vote_tally(&previous_vote, previous_amount, previous_delegated, previous_conviction, parameters.tally_mode);
+            let tally = Self::vote_tally(&vote, new_vote, delegated, conviction, parameters.tally_mode);
+            let mut updated_post_struct = post_struct;
+            Self::remove_from_tally(&mut updated_post_struct, &previous_tally);
+            Self::add_to_tally(&mut updated_post_struct, &tally);
+
+            Posts::<T>::insert(id, updated_post_struct);
+
+            // Emit an event.
+            Self::deposit_event(Event::VoteUpdated {
+                id,
+                voter: who,
+                vote_amount: new_vote,
+                vote,
+            });
+
+            Ok(())
+        }
+
+        pub(crate) fn withdraw_post(
+            who: T::AccountId,
+            id: [u8; 32],
+        ) -> DispatchResult {
+            // Posts can't be used while their storage is being migrated
+            ensure!(!Self::migrating(), Error::<T>::MigrationOngoing);
+
+            // Error if the post does not exist.
+            ensure!(Posts::<T>::contains_key(id), Error::<T>::PostDoesNotExist);
+            let post_struct = Posts::<T>::get(id).expect("Already checked that it exists");
+
+            // Only the submitter can withdraw their post
+            ensure!(post_struct.submitter == who, Error::<T>::NotSubmitter);
+
+            // Resolved posts are ended in `on_idle` instead
+            ensure!(!post_struct.resolved, Error::<T>::PostAlreadyEnded);
+
+            // Error if anyone has voted on the post, including unrevealed commitments
+            ensure!(VoteCounts::<T>::get(id).unwrap_or(0) == 0, Error::<T>::PostHasVotes);
+            ensure!(PostVoters::<T>::iter_key_prefix(id).next().is_none(), Error::<T>::PostHasVotes);
+
+            let parameters = Self::post_parameters(&id);
+
+            // Take the withdrawal fee from the bond, up to the full bond
+            let fee = parameters.withdrawal_fee.min(post_struct.bond);
+            let fee = if fee.is_zero() { fee } else { Self::slash_bond(id, &who, fee)? };
+
+            // Unlock the rest of the bond and the storage rent
+            <<T as Config>::NativeBalance>::release(&HoldReason::PostBond.into(), &who, post_struct.bond.saturating_sub(fee), Precision::BestEffort)?;
+            <<T as Config>::NativeBalance>::release(&HoldReason::StorageRent.into(), &who, parameters.storage_rent, Precision::BestEffort)?;
+
+            // Remove from storage, including the expiry queue
+            Posts::<T>::remove(id);
+            PostParameters::<T>::remove(id);
+            ExpiringPosts::<T>::mutate(Self::resolves_at(post_struct.voting_until, &parameters), |queue| queue.retain(|queued| queued != &id));
+            // The post's text is no longer served once it's withdrawn
+            sp_io::offchain_index::clear(&offchain_post_key(&id));
+
+            // Emit an event.
+            Self::deposit_event(Event::PostWithdrawn {
+                id,
+                submitter: who,
+                fee,
+            });
+
+            Ok(())
+        }
+
+        pub(crate) fn retract_vote(
+            who: T::AccountId,
+            id: [u8; 32],
+        ) -> DispatchResult {
+            // Posts can't be used while their storage is being migrated
+            ensure!(!Self::migrating(), Error::<T>::MigrationOngoing);
+
+            // Error if the post does not exist.
+            ensure!(Posts::<T>::contains_key(id), Error::<T>::PostDoesNotExist);
+            let post_struct = Posts::<T>::get(id).expect("Already checked that it exists");
+
+            let parameters = Self::post_parameters(&id);
+
+            // Commitments and revealed votes can't be retracted
+            ensure!(!parameters.commit_reveal, Error::<T>::CommitRevealRequired);
+
+            // Check if voting is still open for that post
+            ensure!(frame_system::Pallet::<T>::block_number() < post_struct.voting_until, Error::<T>::VotingEnded);
+
+            // Error if this particular vote no longer exists or never existed.
+            ensure!(Votes::<T>::contains_key(&who, id), Error::<T>::VoteDoesNotExist);
+
+            let (amount, vote) = Votes::<T>::take(&who, id);
+
+            // Remove the vote's lock, freezing the largest of the account's remaining votes
+            Self::remove_vote_lock(&who, id)?;
+
+            // Remove the vote from the post's totals, along with its conviction and the balance delegated to the voter
+            // Retracted votes don't count, so they aren't kept locked for their conviction
+            let conviction = VoteConvictions::<T>::take(&who, id);
+            let delegated = DelegatedVotes::<T>::take(&who, id);
+            let tally = Self::vote_tally(&vote, amount, delegated, conviction, parameters.tally_mode);
+            let mut updated_post_struct = post_struct;
+            Self::remove_from_tally(&mut updated_post_struct, &tally);
+
+            Posts::<T>::insert(id, updated_post_struct);
+
+            // Remove them from the voters on this post, freeing up their slot
+            PostVoters::<T>::remove(id, &who);
+
+            // Decrease vote count or remove if 0
+            if let Some(count) = VoteCounts::<T>::get(id) {
+                if count > 1 {
+                    VoteCounts::<T>::insert(id, count - 1)
+                } else {
+                    VoteCounts::<T>::remove(id)
+                }
+            };
+
+            // Emit an event.
+            Self::deposit_event(Event::VoteRetracted {
+                id,
+                voter: who,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        pub(crate) fn commit_vote(
+            who: T::AccountId,
+            id: [u8; 32],
+            commitment: [u8; 32],
+            collateral: BalanceOf<T>,
+        ) -> DispatchResult {
+            // Posts can't be used while their storage is being migrated
+            ensure!(!Self::migrating(), Error::<T>::MigrationOngoing);
+
+            // Error if the post does not exist.
+            ensure!(Posts::<T>::contains_key(id), Error::<T>::PostDoesNotExist);
+            let post_struct = Posts::<T>::get(id).expect("Already checked that it exists");
+
+            let parameters = Self::post_parameters(&id);
+            ensure!(parameters.commit_reveal, Error::<T>::NotCommitReveal);
+
+            // Ensure the collateral is higher than the post's `VoteMinimum`
+            ensure!(collateral >= parameters.vote_minimum, Error::<T>::VoteTooLow);
+
+            // Check if voting is still open for that post
+            ensure!(frame_system::Pallet::<T>::block_number() < post_struct.voting_until, Error::<T>::VotingEnded);
+
+            // Ensure MaxVoters has not been reached, counting unrevealed commitments
+            let commitments = CommitmentCounts::<T>::get(id);
+            ensure!(commitments < T::MaxVoters::get(), Error::<T>::VotersMaxed);
+
+            // Check if they have already committed
+            ensure!(!Commitments::<T>::contains_key(&who, id), Error::<T>::AlreadyVoted);
+
+            // With `VoteCollateral::Freeze` the delegated tokens would also back the account's own vote
+            if T::VoteCollateral::get() == VoteCollateral::Freeze {
+                ensure!(!Delegations::<T>::contains_key(&who), Error::<T>::AlreadyDelegating);
+            }
+
+            // Check if they have enough balance for the freeze
+            ensure!(collateral < <<T as Config>::NativeBalance>::total_balance(&who), Error::<T>::InsufficientFreeBalance);
+
+            // Lock the collateral until the commitment is revealed or forfeited
+            Self::set_vote_lock(&who, id, collateral)?;
+
+            Commitments::<T>::insert(&who, id, (commitment, collateral));
+
+            // Add them to the voters on this post, so their collateral is unlocked when the post is ended
+            PostVoters::<T>::insert(id, &who, ());
+            CommitmentCounts::<T>::insert(id, commitments + 1);
+
+            // Emit an event.
+            Self::deposit_event(Event::VoteCommitted {
+                id,
+                voter: who,
+                collateral,
+            });
+
+            Ok(())
+        }
+
+        pub(crate) fn reveal_vote(
+            who: T::AccountId,
+            id: [u8; 32],
+            vote: VoteChoice<BalanceOf<T>>,
+            vote_amount: BalanceOf<T>,
+            salt: [u8; 32],
+        ) -> DispatchResult {
+            // Posts can't be used while their storage is being migrated
+            ensure!(!Self::migrating(), Error::<T>::MigrationOngoing);
+
+            // Error if the post does not exist.
+            ensure!(Posts::<T>::contains_key(id), Error::<T>::PostDoesNotExist);
+            let post_struct = Posts::<T>::get(id).expect("Already checked that it exists");
+
+            let parameters = Self::post_parameters(&id);
+            ensure!(parameters.commit_reveal, Error::<T>::NotCommitReveal);
+
+            // Commitments are revealed between the end of the voting period and the post's resolution
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                now >= post_struct.voting_until && now < Self::resolves_at(post_struct.voting_until, &parameters),
+                Error::<T>::RevealNotOpen
+            );
+
+            let (commitment, collateral) = Commitments::<T>::get(&who, id).ok_or(Error::<T>::CommitmentDoesNotExist)?;
+            ensure!(Self::vote_commitment(&who, &id, &vote, vote_amount, &salt) == commitment, Error::<T>::InvalidReveal);
+
+            // The amounts of a split vote must add up to the vote
+            Self::ensure_valid_split(&vote, vote_amount, parameters.tally_mode)?;
+
+            // Ensure the vote is higher than the post's `VoteMinimum` and covered by the collateral
+            ensure!(vote_amount >= parameters.vote_minimum, Error::<T>::VoteTooLow);
+            ensure!(vote_amount <= collateral, Error::<T>::RevealExceedsCollateral);
+
+            Commitments::<T>::remove(&who, id);
+
+            // Only keep the revealed amount locked
+            Self::set_vote_lock(&who, id, vote_amount)?;
+
+            // Store vote for account and post
+            Votes::<T>::insert(&who, id, (vote_amount, &vote));
+
+            // Update the number of votes for this post
+            VoteCounts::<T>::mutate(id, |count| *count = Some(count.unwrap_or(0) + 1));
+
+            // Add the vote to the post's totals, with the account's conviction and the balance delegated to the voter
+            let conviction = Self::set_vote_conviction(&who, id, &vote);
+            let delegated = DelegatedBalance::<T>::get(&who);
+            if !delegated.is_zero() {
+                DelegatedVotes::<T>::insert(&who, id, delegated);
+            }
+
+            let tally = Self::vote_tally(&vote, vote_amount, delegated, conviction, parameters.tally_mode);
+            let mut updated_post_struct = post_struct;
+            Self::add_to_tally(&mut updated_post_struct, &tally);
+
+            Posts::<T>::insert(id, updated_post_struct);
+
+            // Emit an event.
+            Self::deposit_event(Event::VoteRevealed {
+                id,
+                voter: who,
+                vote_amount,
+                vote,
+            });
+
+            Ok(())
+        }
+
+        pub(crate) fn delegate_balance(
+            who: T::AccountId,
+            delegate: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            // Posts can't be used while their storage is being migrated
+            ensure!(!Self::migrating(), Error::<T>::MigrationOngoing);
+
+            ensure!(who != delegate, Error::<T>::CannotDelegateToSelf);
+            ensure!(!Delegations::<T>::contains_key(&who), Error::<T>::AlreadyDelegating);
+
+            // With `VoteCollateral::Freeze` the account's votes would be backed by the delegated tokens too
+            if T::VoteCollateral::get() == VoteCollateral::Freeze {
+                ensure!(
+                    !VoteLocksFor::<T>::contains_key(&who) && LegacyVoteLocks::<T>::iter_key_prefix(&who).next().is_none(),
+                    Error::<T>::AlreadyVoting
+                );
+            }
+
+            // Ensure the delegation is higher than the current `VoteMinimum`
+            ensure!(amount >= CurrentParameters::<T>::get().vote_minimum, Error::<T>::VoteTooLow);
+
+            // Check if they have enough balance for the freeze
+            ensure!(amount < <<T as Config>::NativeBalance>::total_balance(&who), Error::<T>::InsufficientFreeBalance);
+
+            Delegations::<T>::insert(&who, (delegate.clone(), amount));
+
+            // Lock the delegated balance the same way as votes
+            match T::VoteCollateral::get() {
+                VoteCollateral::Freeze => Self::update_vote_freeze(&who)?,
+                VoteCollateral::Hold => {
+                    <<T as Config>::NativeBalance>::hold(&HoldReason::Vote.into(), &who, amount)
+                        .map_err(|_| Error::<T>::InsufficientFreeBalance)?;
                 },
-                Direction::Bearish => {
-                    if previous_direction == Direction::Bearish {
-                        Post {
-                            bear_votes: post_struct.bear_votes - previous_amount + new_vote,
-                            ..post_struct
-                        }
-                    } else {
-                        Post {
-                            bull_votes: post_struct.bull_votes - previous_amount,
-                            bear_votes: post_struct.bear_votes + new_vote,
-                            ..post_struct
-                        }
-                    }
+            }
+
+            DelegatedBalance::<T>::mutate(&delegate, |total| total.add(amount));
+            Self::apply_delegation(&delegate, amount, true);
+
+            // Emit an event.
+            Self::deposit_event(Event::Delegated {
+                delegator: who,
+                delegate,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        pub(crate) fn undelegate_balance(
+            who: T::AccountId,
+        ) -> DispatchResult {
+            // Posts can't be used while their storage is being migrated
+            ensure!(!Self::migrating(), Error::<T>::MigrationOngoing);
+
+            let (delegate, amount) = Delegations::<T>::take(&who).ok_or(Error::<T>::NotDelegating)?;
+
+            // Unlock the delegated balance
+            match T::VoteCollateral::get() {
+                VoteCollateral::Freeze => Self::update_vote_freeze(&who)?,
+                VoteCollateral::Hold => {
+                    <<T as Config>::NativeBalance>::release(&HoldReason::Vote.into(), &who, amount, Precision::BestEffort)?;
                 },
-                Direction::Tie => {
-                    if previous_direction == Direction::Bullish {
-                        Post {
-                            bull_votes: post_struct.bull_votes - previous_amount,
-                            ..post_struct
-                        }
-                    } else {
-                        Post {
-                            bear_votes: post_struct.bear_votes - previous_amount,
-                            ..post_struct
-                        }
-                    }
+            }
+
+            DelegatedBalance::<T>::mutate_exists(&delegate, |total| {
+                let mut remaining = total.unwrap_or_default();
+                remaining.remove(amount);
+                *total = if remaining.is_zero() { None } else { Some(remaining) };
+            });
+            Self::apply_delegation(&delegate, amount, false);
+
+            // Emit an event.
+            Self::deposit_event(Event::Undelegated {
+                delegator: who,
+                delegate,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        // Adds or removes delegated balance from the delegate's votes on posts that are still open for voting
+        // Posts whose voting period has ended keep the delegated balance they were voted with
+        pub(crate) fn apply_delegation(
+            delegate: &T::AccountId,
+            amount: BalanceOf<T>,
+            add: bool,
+        ) {
+            let now = frame_system::Pallet::<T>::block_number();
+
+            for (id, _) in VoteLocksFor::<T>::get(delegate) {
+                // Unrevealed commitments aren't counted yet
+                if !Votes::<T>::contains_key(delegate, id) {
+                    continue;
+                }
+                let mut post = match Posts::<T>::get(id) {
+                    Some(post) if !post.resolved && now < post.voting_until => post,
+                    _ => continue,
+                };
+
+                let (vote_amount, vote) = Votes::<T>::get(delegate, id);
+                let conviction = VoteConvictions::<T>::get(delegate, id);
+                let tally_mode = Self::post_parameters(&id).tally_mode;
+                let previous = DelegatedVotes::<T>::get(delegate, id);
+
+                // Never remove more than was counted in this vote
+                let mut counted = previous;
+                if add {
+                    counted.add(amount);
+                } else {
+                    counted.remove(amount);
+                }
+
+                // Tally the vote again with its new delegated balance, as its weight isn't always linear
+                Self::remove_from_tally(&mut post, &Self::vote_tally(&vote, vote_amount, previous, conviction, tally_mode));
+                Self::add_to_tally(&mut post, &Self::vote_tally(&vote, vote_amount, counted, conviction, tally_mode));
+
+                if counted.is_zero() {
+                    DelegatedVotes::<T>::remove(delegate, id);
+                } else {
+                    DelegatedVotes::<T>::insert(delegate, id, counted);
                 }
+                Posts::<T>::insert(id, post);
+            }
+        }
+
+        // Adds a vote's amounts and weights to the post's totals
+        pub(crate) fn add_to_tally(
+            post: &mut Post<T>,
+            tally: &VoteTally<BalanceOf<T>>,
+        ) {
+            post.bull_votes = post.bull_votes.saturating_add(tally.bull_votes);
+            post.bear_votes = post.bear_votes.saturating_add(tally.bear_votes);
+            post.abstain_votes = post.abstain_votes.saturating_add(tally.abstain_votes);
+            post.bull_weight = post.bull_weight.saturating_add(tally.bull_weight);
+            post.bear_weight = post.bear_weight.saturating_add(tally.bear_weight);
+            post.bull_delegated_weight = post.bull_delegated_weight.saturating_add(tally.bull_delegated_weight);
+            post.bear_delegated_weight = post.bear_delegated_weight.saturating_add(tally.bear_delegated_weight);
+        }
+
+        // Removes a vote's amounts and weights from the post's totals
+        pub(crate) fn remove_from_tally(
+            post: &mut Post<T>,
+            tally: &VoteTally<BalanceOf<T>>,
+        ) {
+            post.bull_votes = post.bull_votes.saturating_sub(tally.bull_votes);
+            post.bear_votes = post.bear_votes.saturating_sub(tally.bear_votes);
+            post.abstain_votes = post.abstain_votes.saturating_sub(tally.abstain_votes);
+            post.bull_weight = post.bull_weight.saturating_sub(tally.bull_weight);
+            post.bear_weight = post.bear_weight.saturating_sub(tally.bear_weight);
+            post.bull_delegated_weight = post.bull_delegated_weight.saturating_sub(tally.bull_delegated_weight);
+            post.bear_delegated_weight = post.bear_delegated_weight.saturating_sub(tally.bear_delegated_weight);
+        }
+
+        // What a vote of `amount` tokens adds to its post's totals, along with the balance delegated to the voter
+        // A split vote's delegated balance and weight are divided in proportion to the split
+        pub(crate) fn vote_tally(
+            vote: &VoteChoice<BalanceOf<T>>,
+            amount: BalanceOf<T>,
+            delegated: Delegated<BalanceOf<T>>,
+            conviction: Conviction,
+            tally_mode: TallyMode,
+        ) -> VoteTally<BalanceOf<T>> {
+            let total = amount.saturating_add(delegated.amount);
+            let weight = Self::vote_weight(tally_mode, conviction, amount, delegated);
+            let delegated_weight = weight.saturating_sub(Self::vote_weight(tally_mode, conviction, amount, Default::default()));
+            let mut tally = VoteTally {
+                bull_votes: Zero::zero(),
+                bear_votes: Zero::zero(),
+                abstain_votes: Zero::zero(),
+                bull_weight: Zero::zero(),
+                bear_weight: Zero::zero(),
+                bull_delegated_weight: Zero::zero(),
+                bear_delegated_weight: Zero::zero(),
             };
 
-            Posts::<T>::insert(&id, updated_post_struct);
+            match vote {
+                VoteChoice::Aye => {
+                    tally.bull_votes = total;
+                    tally.bull_weight = weight;
+                    tally.bull_delegated_weight = delegated_weight;
+                },
+                VoteChoice::Nay => {
+                    tally.bear_votes = total;
+                    tally.bear_weight = weight;
+                    tally.bear_delegated_weight = delegated_weight;
+                },
+                VoteChoice::Abstain => tally.abstain_votes = total,
+                VoteChoice::Split { bull, bear } => {
+                    let bull_share = Perbill::from_rational(*bull, bull.saturating_add(*bear));
+                    let bull_delegated = bull_share * delegated.amount;
+                    tally.bull_votes = bull.saturating_add(bull_delegated);
+                    tally.bear_votes = bear.saturating_add(delegated.amount.saturating_sub(bull_delegated));
+                    tally.bull_weight = bull_share * weight;
+                    tally.bear_weight = weight.saturating_sub(tally.bull_weight);
+                    tally.bull_delegated_weight = bull_share * delegated_weight;
+                    tally.bear_delegated_weight = delegated_weight.saturating_sub(tally.bull_delegated_weight);
+                },
+            }
+
+            tally
+        }
+
+        // Ensures a split vote's amounts add up to the vote amount
+        // A vote counted once per account can't be divided, so `OneAccountOneVote` posts don't take split votes
+        pub(crate) fn ensure_valid_split(
+            vote: &VoteChoice<BalanceOf<T>>,
+            amount: BalanceOf<T>,
+            tally_mode: TallyMode,
+        ) -> DispatchResult {
+            if let VoteChoice::Split { bull, bear } = vote {
+                ensure!(tally_mode != TallyMode::OneAccountOneVote, Error::<T>::SplitNotAllowed);
+                ensure!(bull.checked_add(bear) == Some(amount), Error::<T>::InvalidSplit);
+            }
+            Ok(())
+        }
+
+        // The weight a vote adds to its post's weighted total, given the post's tally mode
+        pub(crate) fn vote_weight(
+            tally_mode: TallyMode,
+            conviction: Conviction,
+            amount: BalanceOf<T>,
+            delegated: Delegated<BalanceOf<T>>,
+        ) -> BalanceOf<T> {
+            match tally_mode {
+                TallyMode::Linear => conviction.votes(amount).saturating_add(delegated.amount),
+                // Each delegation counts by its own square root, the same as if it was voted with directly
+                TallyMode::Quadratic => conviction.votes(amount.integer_sqrt()).saturating_add(delegated.quadratic),
+                // Delegating doesn't add accounts to the vote
+                TallyMode::OneAccountOneVote => conviction.votes(One::one()),
+            }
+        }
 
-            // Emit an event.
-            Self::deposit_event(Event::VoteUpdated {
-                id,
-                voter: who,
-                vote_amount: new_vote,
-                direction,
-            });
+        /// The commitment of an account's vote on a post, to submit with `try_commit_vote` and reveal with `try_reveal_vote`
+        pub fn vote_commitment(
+            who: &T::AccountId,
+            id: &[u8; 32],
+            vote: &VoteChoice<BalanceOf<T>>,
+            vote_amount: BalanceOf<T>,
+            salt: &[u8; 32],
+        ) -> [u8; 32] {
+            sp_io::hashing::blake2_256(&(who, id, vote, vote_amount, salt).encode())
+        }
 
-            Ok(())
+        // The block a post can be resolved at, after its voting period and reveal period (for commit-reveal posts)
+        pub(crate) fn resolves_at(
+            voting_until: BlockNumberFor<T>,
+            parameters: &ParametersOf<T>,
+        ) -> BlockNumberFor<T> {
+            if parameters.commit_reveal {
+                voting_until.saturating_add(parameters.reveal_period)
+            } else {
+                voting_until
+            }
         }
 
         pub(crate) fn resolve_voting(
-            post_input: BoundedVec<u8, T::MaxInputLength>
+            id: [u8; 32]
         ) -> DispatchResult {
-            let id = sp_io::hashing::blake2_256(&post_input);
+            // Posts can't be used while their storage is being migrated
+            ensure!(!Self::migrating(), Error::<T>::MigrationOngoing);
 
             // Error if the post does not exist.
-            ensure!(Posts::<T>::contains_key(&id), Error::<T>::PostDoesNotExist);
-            let post_struct = Posts::<T>::get(&id).expect("Already checked that it exists");
+            ensure!(Posts::<T>::contains_key(id), Error::<T>::PostDoesNotExist);
+            let post_struct = Posts::<T>::get(id).expect("Already checked that it exists");
             let submitter = post_struct.submitter.clone();
 
-            // Check if the voting period is over for that post
-            // If current block number is lower than the post's voting_until, voting has not ended; error.
-            ensure!(frame_system::Pallet::<T>::block_number() >= post_struct.voting_until, Error::<T>::VotingStillOngoing);
+            // Check if the voting period (and reveal period, for commit-reveal posts) is over for that post
+            // If current block number is lower than that, voting has not ended; error.
+            let resolves_at = Self::resolves_at(post_struct.voting_until, &Self::post_parameters(&id));
+            ensure!(frame_system::Pallet::<T>::block_number() >= resolves_at, Error::<T>::VotingStillOngoing);
+
+            // Error if the voting was already resolved, either manually or in `on_initialize`
+            ensure!(!post_struct.resolved, Error::<T>::PostAlreadyEnded);
 
             // End the voting and update storage
             let updated_post_struct = Post {
                 resolved: true,
                 ..post_struct
             };
-            Posts::<T>::insert(&id, &updated_post_struct);
+            Posts::<T>::insert(id, &updated_post_struct);
+
+            // Queue the post to be ended in `on_idle`
+            PostsToEnd::<T>::insert(id, ());
 
             // Reward/slash amount
             let bond = post_struct.bond;
+            let parameters = Self::post_parameters(&id);
+            let voter_count = VoteCounts::<T>::get(id).unwrap_or(0);
 
-            // Unlock submitter's bond
-            <<T as Config>::NativeBalance>::release(&HoldReason::PostBond.into(), &submitter, bond, Precision::BestEffort)?;
+            let resolution = Self::resolution(&updated_post_struct, voter_count, &parameters);
+            let result = resolution.result.clone();
+            let turnout = resolution.turnout;
 
-            let result: Direction = if updated_post_struct.bull_votes > updated_post_struct.bear_votes {
-                Direction::Bullish
-            } else if updated_post_struct.bull_votes < updated_post_struct.bear_votes {
-                Direction::Bearish
+            // Slash the submitter's held bond
+            let slashed = if result == Outcome::Bearish {
+                Self::slash_bond(id, &submitter, resolution.slash)?
             } else {
-                Direction::Tie
+                Zero::zero()
             };
 
-            // Reward/slash submitter or do nothing if there is a tie/no votes
-            if result == Direction::Bullish {
+            // Unlock what's left of the submitter's bond
+            <<T as Config>::NativeBalance>::release(&HoldReason::PostBond.into(), &submitter, bond.saturating_sub(slashed), Precision::BestEffort)?;
+
+            // Reward/slash submitter or do nothing if there is a tie/no votes/no quorum
+            if result == Outcome::Bullish {
+                // Set aside the voters' cut of the reward
+                let winning_votes = updated_post_struct.bull_weight.saturating_sub(updated_post_struct.bull_delegated_weight);
+                Self::create_payout_pool(id, Outcome::Bullish, resolution.voter_share, winning_votes);
+
                 // Reward the submitter
-                let rewarded = match T::RewardStyle::get() {
-                    false => Self::reward_flat(&submitter)?,
-                    true => Self::reward_coefficient(&submitter, &bond)?,
-                };
+                let rewarded = Self::pay_reward(&submitter, resolution.reward)?;
 
                 Self::deposit_event(Event::VotingResolved { 
                     id,
@@ -832,13 +2746,15 @@ pub mod pallet {
                     result,
                     rewarded,
                     slashed: Zero::zero(),
+                    turnout,
+                    voter_count,
                 });
-            } else if result == Direction::Bearish {
-                // Slashes the submitter
-                let slashed = match T::SlashStyle::get() {
-                    false => Self::slash_flat(&submitter, bond)?,
-                    true => Self::slash_coefficient(&submitter, &bond)?,
-                };
+            } else if result == Outcome::Bearish {
+                // The slashed tokens are shared among the voters as they're paid out, either from the pot or minted again
+                if parameters.voter_payouts {
+                    let winning_votes = updated_post_struct.bear_weight.saturating_sub(updated_post_struct.bear_delegated_weight);
+                    Self::create_payout_pool(id, Outcome::Bearish, slashed, winning_votes);
+                }
 
                 Self::deposit_event(Event::VotingResolved { 
                     id,
@@ -846,122 +2762,807 @@ pub mod pallet {
                     result,
                     rewarded: Zero::zero(),
                     slashed,
+                    turnout,
+                    voter_count,
                 });
             } else {
-                // Does nothing if tie/no votes
+                // Does nothing if tie/no votes/no quorum
                 Self::deposit_event(Event::VotingResolved { 
                     id,
                     submitter,
-                    result: Direction::Tie,
+                    result,
                     rewarded: Zero::zero(),
                     slashed: Zero::zero(),
+                    turnout,
+                    voter_count,
                 });
             }
 
             Ok(())
         }
         
-        // Reward a flat amount
-        pub(crate) fn reward_flat(who: &T::AccountId) -> Result<BalanceOf<T>, DispatchError> {
-            let reward = T::FlatReward::get().into();
+        // Calculates the result of a post's voting and the resulting reward or slash, without changing any storage
+        pub(crate) fn resolution(
+            post: &Post<T>,
+            voter_count: u32,
+            parameters: &ParametersOf<T>,
+        ) -> ResolutionPreview<BalanceOf<T>> {
+            let turnout = post.bull_votes.saturating_add(post.bear_votes).saturating_add(post.abstain_votes);
+
+            // Posts without enough votes or voters are neither rewarded nor slashed
+            // Otherwise the weighted totals decide the result
+            let result: Outcome = if turnout < parameters.quorum_votes || voter_count < parameters.quorum_voters {
+                Outcome::NoQuorum
+            } else if post.bull_weight > post.bear_weight {
+                Outcome::Bullish
+            } else if post.bull_weight < post.bear_weight {
+                Outcome::Bearish
+            } else {
+                Outcome::Tie
+            };
+
+            let mut preview = ResolutionPreview {
+                result: result.clone(),
+                reward: Zero::zero(),
+                slash: Zero::zero(),
+                voter_share: Zero::zero(),
+                turnout,
+                voter_count,
+            };
 
-            // Reward the submitter
-            <<T as Config>::NativeBalance>::mint_into(&who, reward)?;
+            match result {
+                Outcome::Bullish => {
+                    let reward = T::RewardPolicy::reward(post, voter_count, parameters);
 
-            Ok(reward)
+                    // The voters' cut of the reward
+                    if parameters.voter_payouts {
+                        preview.voter_share = parameters.voter_reward_cut * reward;
+                    }
+                    preview.reward = reward.saturating_sub(preview.voter_share);
+                },
+                Outcome::Bearish => {
+                    // Slash the submitter up to their full bond amount, but not beyond
+                    preview.slash = T::SlashPolicy::slash(post, voter_count, parameters).min(post.bond);
+
+                    if parameters.voter_payouts {
+                        preview.voter_share = preview.slash;
+                    }
+                },
+                Outcome::Tie | Outcome::NoQuorum => {},
+            }
+
+            preview
         }
-        
-        // Reward based on a coefficient and how much they bonded
-        pub(crate) fn reward_coefficient(who: &T::AccountId, bond: &BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
-            let reward = Permill::from_percent(T::RewardCoefficient::get()) * *bond;
 
-            // Reward the submitter
-            <<T as Config>::NativeBalance>::mint_into(&who, reward)?;
+        /// A post's details, if it exists
+        pub fn post_details(id: [u8; 32]) -> Option<PostDetails<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>> {
+            Posts::<T>::get(id).map(|post| PostDetails {
+                id,
+                submitter: post.submitter,
+                bond: post.bond,
+                bull_votes: post.bull_votes,
+                bear_votes: post.bear_votes,
+                abstain_votes: post.abstain_votes,
+                bull_weight: post.bull_weight,
+                bear_weight: post.bear_weight,
+                voting_until: post.voting_until,
+                resolved: post.resolved,
+                voter_count: VoteCounts::<T>::get(id).unwrap_or(0),
+            })
+        }
 
-            Ok(reward)
+        /// An account's votes, as (post ID, amount, choice)
+        #[allow(clippy::type_complexity)]
+        pub fn account_votes(who: &T::AccountId) -> Vec<([u8; 32], BalanceOf<T>, VoteChoice<BalanceOf<T>>)> {
+            Votes::<T>::iter_prefix(who)
+                .map(|(id, (amount, vote))| (id, amount, vote))
+                .collect()
         }
 
-        // Slash a flat amount
-        pub(crate) fn slash_flat(who: &T::AccountId, bond: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
-            let flat_slash = T::FlatSlash::get().into();
-            
-            // Slash the submitter up to their full bond amount, but not beyond
-            if bond < flat_slash {
-                <<T as Config>::NativeBalance>::burn_from(&who, bond, Preservation::Protect, Precision::BestEffort, Fortitude::Force)?;
-                Ok(bond)
-            } else {
-                <<T as Config>::NativeBalance>::burn_from(&who, flat_slash, Preservation::Protect, Precision::BestEffort, Fortitude::Force)?;
-                Ok(flat_slash)
+        /// The accounts that voted on a post
+        pub fn post_voters(id: [u8; 32]) -> Vec<T::AccountId> {
+            PostVoters::<T>::iter_key_prefix(id).collect()
+        }
+
+        /// What resolving a post's voting would produce right now, if the post exists and hasn't been resolved yet
+        /// The reward doesn't account for a pot shortfall
+        pub fn preview_resolution(id: [u8; 32]) -> Option<ResolutionPreview<BalanceOf<T>>> {
+            let post = Posts::<T>::get(id).filter(|post| !post.resolved)?;
+            let voter_count = VoteCounts::<T>::get(id).unwrap_or(0);
+
+            Some(Self::resolution(&post, voter_count, &Self::post_parameters(&id)))
+        }
+
+        // The parameters a post was submitted under, falling back to the current ones for posts submitted before they were tracked
+        pub(crate) fn post_parameters(id: &[u8; 32]) -> ParametersOf<T> {
+            PostParameters::<T>::get(id).unwrap_or_else(CurrentParameters::<T>::get)
+        }
+
+        // Checks that the parameters are within bounds
+        pub(crate) fn validate_parameters(parameters: &ParametersOf<T>) -> DispatchResult {
+            ensure!(parameters.reward_coefficient <= 100, Error::<T>::InvalidParameters);
+            ensure!(parameters.slash_coefficient <= 100, Error::<T>::InvalidParameters);
+            ensure!(!parameters.voting_period.is_zero(), Error::<T>::InvalidParameters);
+            ensure!(!parameters.bond_minimum.is_zero(), Error::<T>::InvalidParameters);
+            ensure!(!parameters.vote_minimum.is_zero(), Error::<T>::InvalidParameters);
+            ensure!(!parameters.commit_reveal || !parameters.reveal_period.is_zero(), Error::<T>::InvalidParameters);
+            Ok(())
+        }
+
+        // Resolves the voting of due posts from the expiry queue, up to `MaxResolutionsPerBlock`
+        // scanning at most `MaxExpiryBlocksScanned` blocks of the queue
+        // Posts that don't fit in this block's budget stay queued and the cursor stays on their block
+        pub(crate) fn resolve_expired(now: BlockNumberFor<T>) -> Weight {
+            let mut cursor = match ExpiryCursor::<T>::get() {
+                Some(cursor) => cursor,
+                None => return T::DbWeight::get().reads(1),
+            };
+
+            let limit = T::MaxResolutionsPerBlock::get();
+            let max_scanned = T::MaxExpiryBlocksScanned::get() as u64;
+            let mut processed = 0u32;
+            let mut blocks_scanned = 0u64;
+
+            while cursor <= now && processed < limit && blocks_scanned < max_scanned {
+                let mut queue = ExpiringPosts::<T>::take(cursor);
+                blocks_scanned += 1;
+
+                while processed < limit {
+                    match queue.pop() {
+                        Some(id) => {
+                            // Posts resolved manually with `try_resolve_voting` are skipped
+                            if Posts::<T>::get(id).is_some_and(|post| !post.resolved) {
+                                // A failed resolution is rolled back and isn't queued again, so it doesn't fail every block
+                                // It's left for `try_resolve_voting` to retry once whatever failed is fixed
+                                if let Err(error) = with_storage_layer(|| Self::resolve_voting(id)) {
+                                    Self::deposit_event(Event::ResolutionFailed { id, error });
+                                }
+                            }
+                            processed += 1;
+                        },
+                        None => break
+                    }
+                }
+
+                if !queue.is_empty() {
+                    // Budget used up, carry the rest over to the next block
+                    ExpiringPosts::<T>::insert(cursor, queue);
+                    break;
+                }
+                cursor = cursor.saturating_add(One::one());
+            }
+
+            ExpiryCursor::<T>::put(cursor);
+
+            T::WeightInfo::on_initialize_resolve(processed)
+                .saturating_add(T::DbWeight::get().reads_writes(blocks_scanned, blocks_scanned))
+        }
+
+        /// The account of the pot, if `PotId` is set
+        pub fn pot_account() -> Option<T::AccountId> {
+            T::PotId::get().map(|id| id.into_account_truncating())
+        }
+
+        /// The balance of the pot, if `PotId` is set
+        pub fn pot_balance() -> Option<BalanceOf<T>> {
+            Self::pot_account().map(|pot| <<T as Config>::NativeBalance>::balance(&pot))
+        }
+
+        // Takes `amount` from the submitter's held bond, into the pot if there is one and burning it otherwise
+        // Used for slashes and withdrawal fees, returns the amount taken
+        pub(crate) fn slash_bond(
+            id: [u8; 32],
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            match Self::pot_account() {
+                Some(pot) => {
+                    let slashed = <<T as Config>::NativeBalance>::transfer_on_hold(&HoldReason::PostBond.into(), who, &pot, amount, Precision::BestEffort, Restriction::Free, Fortitude::Force)?;
+
+                    Self::deposit_event(Event::SlashedToPot {
+                        id,
+                        amount: slashed,
+                    });
+
+                    Ok(slashed)
+                },
+                None => <<T as Config>::NativeBalance>::burn_held(&HoldReason::PostBond.into(), who, amount, Precision::BestEffort, Fortitude::Force),
             }
         }
 
-        // Slash based on a coefficient and how much they bonded
-        pub(crate) fn slash_coefficient(who: &T::AccountId, bond: &BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
-            let percent = if T::SlashCoefficient::get() <= 100 {
-                T::SlashCoefficient::get()
-            } else {
-                100
+        // Takes `amount` of an unrevealed commitment's collateral, into the pot if there is one and burning it otherwise
+        // Must be called while the collateral is still locked, so it's taken out of the locked tokens
+        // Returns the amount taken, which can be less than `amount` if a frozen collateral was also held elsewhere
+        pub(crate) fn forfeit_collateral(
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            if amount.is_zero() {
+                return Ok(amount);
+            }
+
+            match (T::VoteCollateral::get(), Self::pot_account()) {
+                (VoteCollateral::Hold, Some(pot)) => <<T as Config>::NativeBalance>::transfer_on_hold(&HoldReason::Vote.into(), who, &pot, amount, Precision::BestEffort, Restriction::Free, Fortitude::Force),
+                (VoteCollateral::Hold, None) => <<T as Config>::NativeBalance>::burn_held(&HoldReason::Vote.into(), who, amount, Precision::BestEffort, Fortitude::Force),
+                // Frozen collateral is taken regardless of the freeze
+                (VoteCollateral::Freeze, Some(pot)) => {
+                    let forfeited = <<T as Config>::NativeBalance as fungible::Unbalanced<T::AccountId>>::decrease_balance(who, amount, Precision::BestEffort, Preservation::Preserve, Fortitude::Force)?;
+                    <<T as Config>::NativeBalance as fungible::Unbalanced<T::AccountId>>::increase_balance(&pot, forfeited, Precision::BestEffort)?;
+                    Ok(forfeited)
+                },
+                (VoteCollateral::Freeze, None) => <<T as Config>::NativeBalance>::burn_from(who, amount, Preservation::Preserve, Precision::BestEffort, Fortitude::Force),
+            }
+        }
+
+        // Pays `amount` to `who`, out of the pot if there is one and minting it otherwise
+        // Returns the amount paid, which is less than `amount` if the pot can't cover it
+        pub(crate) fn pay_reward(
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let pot = match Self::pot_account() {
+                Some(pot) => pot,
+                None => {
+                    <<T as Config>::NativeBalance>::mint_into(who, amount)?;
+                    return Ok(amount);
+                }
             };
-            
-            let slash = Percent::from_percent(percent) * *bond;
-            
-            // Slashes the submitter
-            <<T as Config>::NativeBalance>::burn_from(&who, slash, Preservation::Protect, Precision::BestEffort, Fortitude::Force)?;
-            
-            Ok(slash)
+
+            // Pay as much as possible out of the pot, keeping it alive
+            let available = <<T as Config>::NativeBalance>::reducible_balance(&pot, Preservation::Preserve, Fortitude::Polite);
+            let mut paid = amount.min(available);
+            if !paid.is_zero() {
+                <<T as Config>::NativeBalance>::transfer(&pot, who, paid, Preservation::Preserve)?;
+            }
+
+            if paid < amount {
+                if T::PotShortfall::get() == ShortfallBehaviour::MintUpToCap {
+                    paid = paid.saturating_add(Self::mint_shortfall(who, amount.saturating_sub(paid))?);
+                }
+
+                if paid < amount {
+                    Self::deposit_event(Event::PotShortfall {
+                        who: who.clone(),
+                        requested: amount,
+                        paid,
+                    });
+                }
+            }
+
+            Ok(paid)
+        }
+
+        // Mints up to `amount` to `who`, within what's left of the current period's `ShortfallMintCap`
+        // Returns the amount minted
+        pub(crate) fn mint_shortfall(
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let period = frame_system::Pallet::<T>::block_number() / T::ShortfallMintPeriod::get().max(One::one());
+            let (last_period, minted) = PeriodShortfallMinted::<T>::get();
+            let minted = if last_period == period { minted } else { Zero::zero() };
+
+            let mintable = amount.min(T::ShortfallMintCap::get().saturating_sub(minted));
+            if mintable.is_zero() {
+                return Ok(mintable);
+            }
+
+            <<T as Config>::NativeBalance>::mint_into(who, mintable)?;
+            PeriodShortfallMinted::<T>::put((period, minted.saturating_add(mintable)));
+
+            Self::deposit_event(Event::PotShortfallMinted {
+                who: who.clone(),
+                amount: mintable,
+            });
+
+            Ok(mintable)
+        }
+
+        // Sets aside `amount` to be shared among the voters who voted for the `direction` outcome
+        pub(crate) fn create_payout_pool(
+            id: [u8; 32],
+            direction: Outcome,
+            amount: BalanceOf<T>,
+            winning_votes: BalanceOf<T>,
+        ) {
+            if amount.is_zero() || winning_votes.is_zero() {
+                return;
+            }
+
+            PayoutPools::<T>::insert(id, PayoutPool {
+                direction: direction.clone(),
+                remaining: amount,
+                total: amount,
+                winning_votes,
+            });
+
+            Self::deposit_event(Event::PayoutPoolCreated {
+                id,
+                direction,
+                amount,
+            });
+        }
+
+        // Pays a voter their share of a post's payout pool, by the weight of their vote on the winning side
+        // The weight of the balance delegated to it isn't paid out, as it's the delegators' stake rather than the voter's
+        pub(crate) fn pay_out_voter(
+            who: &T::AccountId,
+            id: [u8; 32],
+            tally: &VoteTally<BalanceOf<T>>,
+        ) {
+            let mut pool = match PayoutPools::<T>::get(id) {
+                Some(pool) => pool,
+                None => return,
+            };
+            let amount = match pool.direction {
+                Outcome::Bullish => tally.bull_weight.saturating_sub(tally.bull_delegated_weight),
+                Outcome::Bearish => tally.bear_weight.saturating_sub(tally.bear_delegated_weight),
+                Outcome::Tie | Outcome::NoQuorum => return,
+            };
+
+            // Share of the pool proportional to the voter's share of the winning votes, never more than what's left
+            let payout = (Perbill::from_rational(amount, pool.winning_votes) * pool.total).min(pool.remaining);
+            if payout.is_zero() {
+                return;
+            }
+
+            // A failed payout shouldn't stop the vote from being unfrozen
+            let payout = with_storage_layer(|| Self::pay_reward(who, payout)).unwrap_or_else(|_| Zero::zero());
+            if !payout.is_zero() {
+                pool.remaining = pool.remaining.saturating_sub(payout);
+                PayoutPools::<T>::insert(id, pool);
+
+                Self::deposit_event(Event::VoterPaidOut {
+                    id,
+                    voter: who.clone(),
+                    amount: payout,
+                });
+            }
         }
 
+        // Unfreezes up to `limit` votes on a resolved post, ending it once all votes are unfrozen
+        // Returns the number of votes unfrozen and whether the post was ended
         pub(crate) fn end_post(
-            post_input: BoundedVec<u8, T::MaxInputLength>
-        ) -> DispatchResultWithPostInfo {
-            let id = sp_io::hashing::blake2_256(&post_input);
+            id: [u8; 32],
+            limit: u32,
+        ) -> Result<(u32, bool), DispatchError> {
+            // Posts can't be used while their storage is being migrated
+            ensure!(!Self::migrating(), Error::<T>::MigrationOngoing);
 
             // Error if the post does not exist.
-            ensure!(Posts::<T>::contains_key(&id), Error::<T>::PostDoesNotExist);
-            let post_struct = Posts::<T>::get(&id).expect("Already checked that it exists");
+            ensure!(Posts::<T>::contains_key(id), Error::<T>::PostDoesNotExist);
+            let post_struct = Posts::<T>::get(id).expect("Already checked that it exists");
 
             // Error if the voting is unresolved
             ensure!(post_struct.resolved, Error::<T>::VotingUnresolved);
 
             let mut unfreeze_count = 0u32;
 
-            // Assume all will be unfrozen
-            let mut all_unfrozen = true;
-
-            // Call unfreeze_vote() for each voter and remove from `Voters` up to `UnfreezeLimit` or until all voters are removed
-            if let Some(mut voters) = Voters::<T>::take(id) {
-                while !(unfreeze_count >= T::UnfreezeLimit::get()) {
-                    match voters.pop() {
-                        Some(voter) => {
-                            Self::unfreeze_vote(voter, id)?;
-                            unfreeze_count += 1;
-                        },
-                        None => break
-                    }
-                }
-                if unfreeze_count >= T::UnfreezeLimit::get() {
-                    all_unfrozen = false;
-                    Voters::<T>::insert(id, voters);
-                }
+            // Call unfreeze_vote() for each voter and remove from `PostVoters` up to `limit` or until all voters are removed
+            // Voters are collected first, as storage can't be changed while iterating over it
+            let voters: Vec<T::AccountId> = PostVoters::<T>::iter_key_prefix(id).take(limit as usize).collect();
+            for voter in voters {
+                PostVoters::<T>::remove(id, &voter);
+                Self::unfreeze_vote(voter, id)?;
+                unfreeze_count += 1;
             }
 
+            // The post is ended once no voters are left
+            let all_unfrozen = PostVoters::<T>::iter_key_prefix(id).next().is_none();
+
             if all_unfrozen {
                 // Unlock the storage rent of the submitter
-                <<T as Config>::NativeBalance>::release(&HoldReason::StorageRent.into(), &post_struct.submitter, T::StorageRent::get().into(), Precision::BestEffort)?;
+                let storage_rent = Self::post_parameters(&id).storage_rent;
+                <<T as Config>::NativeBalance>::release(&HoldReason::StorageRent.into(), &post_struct.submitter, storage_rent, Precision::BestEffort)?;
 
-                // Remove from Posts storage
+                // Remove from Posts storage, along with any dust left in the payout pool
                 let _ = Posts::<T>::take(id);
+                PostsToEnd::<T>::remove(id);
+                PayoutPools::<T>::remove(id);
+                PostParameters::<T>::remove(id);
+                CommitmentCounts::<T>::remove(id);
 
                 // Emit an event
                 Self::deposit_event(Event::PostEnded {
                     id,
                 });
-                Ok(Some(T::WeightInfo::try_end_post(unfreeze_count)).into())
             } else {
                 Self::deposit_event(Event::PostPartiallyEnded {
                     id,
                 });
-                Ok(().into())
+            }
+
+            Ok((unfreeze_count, all_unfrozen))
+        }
+
+        // Checks that the pallet's storage items are consistent with each other:
+        // - every vote and commitment is on an existing post, listed in `PostVoters` and locked by its amount
+        // - the tallies of unresolved posts are the sum of their votes, with their delegated balance and conviction
+        // - `VoteCounts` is the number of votes on each post, and `PostVoters` the number of votes and commitments
+        // - submitters' holds cover the bonds of their unresolved posts and the storage rent of all of them
+        // - the vote freeze or hold of each account covers its vote locks, conviction locks and delegation
+        #[cfg(any(feature = "try-runtime", test))]
+        pub(crate) fn do_try_state() -> Result<(), TryRuntimeError> {
+            use scale_info::prelude::collections::{BTreeMap, BTreeSet};
+            use frame_support::traits::fungible::{InspectFreeze, InspectHold};
+
+            // Entries in the old layout can't be decoded until the migration completes
+            if Self::migrating() {
+                return Ok(());
+            }
+
+            let is_locked = |who: &T::AccountId, id: &[u8; 32], amount: BalanceOf<T>| {
+                VoteLocksFor::<T>::get(who).iter().any(|(post, locked)| post == id && *locked == amount) ||
+                    LegacyVoteLocks::<T>::get(who, id) == Some(amount)
+            };
+
+            // The summed tallies and number of votes, and the number of commitments, on each post
+            let mut tallies: BTreeMap<[u8; 32], (VoteTally<BalanceOf<T>>, u32)> = BTreeMap::new();
+            let mut commitments: BTreeMap<[u8; 32], u32> = BTreeMap::new();
+
+            for (who, id, (amount, vote)) in Votes::<T>::iter() {
+                ensure!(Posts::<T>::contains_key(id), "Vote on a post that doesn't exist");
+                ensure!(PostVoters::<T>::contains_key(id, &who), "Vote missing from PostVoters");
+                ensure!(is_locked(&who, &id, amount), "Vote isn't locked by its amount");
+
+                let delegated = DelegatedVotes::<T>::get(&who, id);
+                let conviction = VoteConvictions::<T>::get(&who, id);
+                let tally = Self::vote_tally(&vote, amount, delegated, conviction, Self::post_parameters(&id).tally_mode);
+
+                let (total, votes) = tallies.entry(id).or_default();
+                total.bull_votes.saturating_accrue(tally.bull_votes);
+                total.bear_votes.saturating_accrue(tally.bear_votes);
+                total.abstain_votes.saturating_accrue(tally.abstain_votes);
+                total.bull_weight.saturating_accrue(tally.bull_weight);
+                total.bear_weight.saturating_accrue(tally.bear_weight);
+                total.bull_delegated_weight.saturating_accrue(tally.bull_delegated_weight);
+                total.bear_delegated_weight.saturating_accrue(tally.bear_delegated_weight);
+                *votes += 1;
+            }
+
+            for (who, id, (_, collateral)) in Commitments::<T>::iter() {
+                ensure!(Posts::<T>::contains_key(id), "Commitment on a post that doesn't exist");
+                ensure!(PostVoters::<T>::contains_key(id, &who), "Commitment missing from PostVoters");
+                ensure!(is_locked(&who, &id, collateral), "Commitment isn't locked by its collateral");
+                *commitments.entry(id).or_default() += 1;
+            }
+
+            // Convictions and delegated balances only exist alongside their vote
+            for (who, id, _) in VoteConvictions::<T>::iter() {
+                ensure!(Votes::<T>::contains_key(&who, id), "Conviction without a vote");
+            }
+            for (who, id, _) in DelegatedVotes::<T>::iter() {
+                ensure!(Votes::<T>::contains_key(&who, id), "Delegated balance without a vote");
+            }
+            for id in PostVoters::<T>::iter_keys().map(|(id, _)| id).chain(VoteCounts::<T>::iter_keys()) {
+                ensure!(Posts::<T>::contains_key(id), "Voters on a post that doesn't exist");
+            }
+
+            // The bonds and storage rent each submitter should have held
+            let mut held: BTreeMap<T::AccountId, (BalanceOf<T>, BalanceOf<T>)> = BTreeMap::new();
+
+            for (id, post) in Posts::<T>::iter() {
+                let (tally, votes) = tallies.remove(&id).unwrap_or_default();
+                let commitments = commitments.get(&id).copied().unwrap_or(0);
+
+                ensure!(VoteCounts::<T>::get(id).unwrap_or(0) == votes, "VoteCounts doesn't match the votes on the post");
+                ensure!(
+                    PostVoters::<T>::iter_key_prefix(id).count() as u32 == votes.saturating_add(commitments),
+                    "PostVoters doesn't match the votes and commitments on the post"
+                );
+
+                // Resolved posts keep the tallies they were resolved with while their votes are unfrozen
+                if !post.resolved {
+                    ensure!(
+                        (post.bull_votes, post.bear_votes, post.abstain_votes) == (tally.bull_votes, tally.bear_votes, tally.abstain_votes),
+                        "Post tallies don't match its votes"
+                    );
+                    ensure!(
+                        (post.bull_weight, post.bear_weight) == (tally.bull_weight, tally.bear_weight),
+                        "Post weighted tallies don't match its votes"
+                    );
+                    ensure!(
+                        (post.bull_delegated_weight, post.bear_delegated_weight) == (tally.bull_delegated_weight, tally.bear_delegated_weight),
+                        "Post delegated weights don't match its votes"
+                    );
+                }
+
+                let (bonds, rent) = held.entry(post.submitter).or_default();
+                if !post.resolved {
+                    bonds.saturating_accrue(post.bond);
+                }
+                rent.saturating_accrue(Self::post_parameters(&id).storage_rent);
+            }
+
+            for (who, (bonds, rent)) in held {
+                ensure!(
+                    <<T as Config>::NativeBalance>::balance_on_hold(&HoldReason::PostBond.into(), &who) >= bonds,
+                    "Bond hold doesn't cover the submitter's bonds"
+                );
+                ensure!(
+                    <<T as Config>::NativeBalance>::balance_on_hold(&HoldReason::StorageRent.into(), &who) >= rent,
+                    "Storage rent hold doesn't cover the submitter's posts"
+                );
+            }
+
+            // Every vote lock is backing a vote or commitment
+            for (who, locks) in VoteLocksFor::<T>::iter() {
+                for (id, _) in locks {
+                    ensure!(
+                        Votes::<T>::contains_key(&who, id) || Commitments::<T>::contains_key(&who, id),
+                        "Vote lock without a vote or commitment"
+                    );
+                }
+            }
+            for (who, id, _) in LegacyVoteLocks::<T>::iter() {
+                ensure!(Votes::<T>::contains_key(&who, id), "Legacy vote lock without a vote");
+            }
+
+            // Every account's locks are backed by its vote freeze or hold
+            let accounts: BTreeSet<T::AccountId> = VoteLocksFor::<T>::iter_keys()
+                .chain(LockExpiries::<T>::iter_keys())
+                .chain(LegacyVoteLocks::<T>::iter_keys().map(|(who, _)| who))
+                .chain(Delegations::<T>::iter_keys())
+                .collect();
+
+            for who in accounts {
+                let delegated = Delegations::<T>::get(&who).map_or_else(Zero::zero, |(_, amount)| amount);
+                let locks = VoteLocksFor::<T>::get(&who)
+                    .into_iter()
+                    .map(|(_, amount)| amount)
+                    .chain(LockExpiries::<T>::get(&who).into_iter().map(|(_, amount)| amount))
+                    .chain(LegacyVoteLocks::<T>::iter_prefix_values(&who));
+
+                match T::VoteCollateral::get() {
+                    VoteCollateral::Freeze => {
+                        let largest = locks.max().unwrap_or_else(Zero::zero).max(delegated);
+                        ensure!(
+                            <<T as Config>::NativeBalance>::balance_frozen(&FreezeReason::Vote.into(), &who) >= largest,
+                            "Vote freeze doesn't cover the account's locks"
+                        );
+                    },
+                    VoteCollateral::Hold => {
+                        let total = locks.fold(delegated, |total, amount| total.saturating_add(amount));
+                        ensure!(
+                            <<T as Config>::NativeBalance>::balance_on_hold(&HoldReason::Vote.into(), &who) >= total,
+                            "Vote hold doesn't cover the account's locks"
+                        );
+                    },
+                }
+            }
+
+            Ok(())
+        }
+
+        // Whether the runtime's multi-block migrations are running, pausing the pallet
+        pub(crate) fn migrating() -> bool {
+            <T as frame_system::Config>::MultiBlockMigrator::ongoing()
+        }
+
+        // Ends resolved posts from `PostsToEnd` for as long as `remaining_weight` allows
+        pub(crate) fn end_posts_idle(remaining_weight: Weight) -> Weight {
+            // Weight of ending a post without any votes, and of each additional vote unfrozen
+            let base_weight = T::WeightInfo::on_idle_end_post(0);
+            let vote_weight = T::WeightInfo::on_idle_end_post(1).saturating_sub(base_weight);
+
+            let mut used_weight = T::DbWeight::get().reads(1);
+
+            while remaining_weight.all_gte(used_weight.saturating_add(base_weight).saturating_add(vote_weight)) {
+                let id = match PostsToEnd::<T>::iter_keys().next() {
+                    Some(id) => id,
+                    None => break,
+                };
+                used_weight.saturating_accrue(T::DbWeight::get().reads(1));
+
+                // Number of votes that fit in the weight that is left
+                let available = remaining_weight.saturating_sub(used_weight).saturating_sub(base_weight);
+                let limit = (available.ref_time() / vote_weight.ref_time().max(1))
+                    .min(available.proof_size() / vote_weight.proof_size().max(1))
+                    .min(T::UnfreezeLimit::get() as u64) as u32;
+
+                match with_storage_layer(|| Self::end_post(id, limit)) {
+                    Ok((unfreeze_count, ended)) => {
+                        used_weight.saturating_accrue(T::WeightInfo::on_idle_end_post(unfreeze_count));
+                        // Out of weight, continue in a later block
+                        if !ended {
+                            break;
+                        }
+                    },
+                    Err(error) => {
+                        // The post can't be ended, drop it from the queue so it doesn't block the others
+                        // Its votes stay locked until `try_end_post` ends it once whatever failed is fixed
+                        PostsToEnd::<T>::remove(id);
+                        Self::deposit_event(Event::EndPostFailed { id, error });
+                        used_weight.saturating_accrue(base_weight);
+                    },
+                }
+            }
+
+            used_weight
+        }
+
+        // Sets the amount locked by an account's vote on a post, then updates the account's freeze or vote hold
+        pub(crate) fn set_vote_lock(
+            who: &T::AccountId,
+            id: [u8; 32],
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            // Votes that didn't fit in `VoteLocksFor` when it was migrated keep their lock where it is
+            let previous = match LegacyVoteLocks::<T>::get(who, id) {
+                Some(previous) => {
+                    LegacyVoteLocks::<T>::insert(who, id, amount);
+                    previous
+                },
+                None => VoteLocksFor::<T>::try_mutate(who, |locks| -> Result<BalanceOf<T>, DispatchError> {
+                    match locks.iter_mut().find(|(post, _)| *post == id) {
+                        Some(lock) => Ok(core::mem::replace(&mut lock.1, amount)),
+                        None => {
+                            locks.try_push((id, amount)).map_err(|_| Error::<T>::TooManyVotes)?;
+                            Ok(Zero::zero())
+                        },
+                    }
+                })?,
+            };
+
+            match T::VoteCollateral::get() {
+                VoteCollateral::Freeze => Self::update_vote_freeze(who),
+                VoteCollateral::Hold => {
+                    // Only hold or release the difference with the previous vote
+                    if amount > previous {
+                        <<T as Config>::NativeBalance>::hold(&HoldReason::Vote.into(), who, amount - previous)
+                            .map_err(|_| Error::<T>::InsufficientFreeBalance)?;
+                    } else if previous > amount {
+                        <<T as Config>::NativeBalance>::release(&HoldReason::Vote.into(), who, previous - amount, Precision::BestEffort)?;
+                    }
+                    Ok(())
+                },
+            }
+        }
+
+        // Removes the lock of an account's vote on a post from `VoteLocksFor` (or `LegacyVoteLocks`), returning the amount it locked
+        pub(crate) fn take_vote_lock(
+            who: &T::AccountId,
+            id: [u8; 32],
+        ) -> BalanceOf<T> {
+            if let Some(removed) = LegacyVoteLocks::<T>::take(who, id) {
+                return removed;
+            }
+
+            VoteLocksFor::<T>::mutate_exists(who, |maybe_locks| {
+                let mut removed = Zero::zero();
+                if let Some(locks) = maybe_locks {
+                    if let Some(index) = locks.iter().position(|(post, _)| *post == id) {
+                        removed = locks.remove(index).1;
+                    }
+                    if locks.is_empty() {
+                        *maybe_locks = None;
+                    }
+                }
+                removed
+            })
+        }
+
+        // Removes the lock of an account's vote on a post, then updates the account's freeze or vote hold
+        pub(crate) fn remove_vote_lock(
+            who: &T::AccountId,
+            id: [u8; 32],
+        ) -> DispatchResult {
+            let removed = Self::take_vote_lock(who, id);
+
+            match T::VoteCollateral::get() {
+                VoteCollateral::Freeze => Self::update_vote_freeze(who),
+                VoteCollateral::Hold => {
+                    <<T as Config>::NativeBalance>::release(&HoldReason::Vote.into(), who, removed, Precision::BestEffort)?;
+                    Ok(())
+                },
+            }
+        }
+
+        // Moves the lock of an account's vote on an ended post to `LockExpiries`, keeping it locked until `until`
+        // If the account has no room left, the lock is merged into the one that expires last
+        pub(crate) fn extend_vote_lock(
+            who: &T::AccountId,
+            id: [u8; 32],
+            until: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let amount = Self::take_vote_lock(who, id);
+
+            if !amount.is_zero() {
+                LockExpiries::<T>::mutate(who, |expiries| {
+                    if expiries.try_push((until, amount)).is_err() {
+                        if let Some(last) = expiries.iter_mut().max_by_key(|(expiry, _)| *expiry) {
+                            last.0 = last.0.max(until);
+                            // Frozen tokens back every lock at once, held tokens back only one
+                            last.1 = match T::VoteCollateral::get() {
+                                VoteCollateral::Freeze => last.1.max(amount),
+                                VoteCollateral::Hold => last.1.saturating_add(amount),
+                            };
+                        }
+                    }
+                });
+            }
+
+            // The vote's hold is kept until the lock lapses
+            match T::VoteCollateral::get() {
+                VoteCollateral::Freeze => Self::update_vote_freeze(who),
+                VoteCollateral::Hold => Ok(()),
+            }
+        }
+
+        // Removes an account's lapsed conviction locks, then updates the account's freeze or vote hold
+        pub(crate) fn unlock_expired(who: T::AccountId) -> DispatchResult {
+            let now = frame_system::Pallet::<T>::block_number();
+
+            let amount = LockExpiries::<T>::try_mutate_exists(&who, |maybe_expiries| -> Result<BalanceOf<T>, DispatchError> {
+                let expiries = maybe_expiries.as_mut().ok_or(Error::<T>::NothingToUnlock)?;
+
+                let mut amount: BalanceOf<T> = Zero::zero();
+                let previous_len = expiries.len();
+                expiries.retain(|(until, locked)| {
+                    let lapsed = *until <= now;
+                    if lapsed {
+                        amount = amount.saturating_add(*locked);
+                    }
+                    !lapsed
+                });
+                ensure!(expiries.len() < previous_len, Error::<T>::NothingToUnlock);
+
+                if expiries.is_empty() {
+                    *maybe_expiries = None;
+                }
+                Ok(amount)
+            })?;
+
+            match T::VoteCollateral::get() {
+                VoteCollateral::Freeze => Self::update_vote_freeze(&who)?,
+                VoteCollateral::Hold => {
+                    <<T as Config>::NativeBalance>::release(&HoldReason::Vote.into(), &who, amount, Precision::BestEffort)?;
+                },
+            }
+
+            // Emit an event.
+            Self::deposit_event(Event::Unlocked {
+                who,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        // Stores the account's conviction for its vote on a post, returning it
+        // Abstaining doesn't add weight, so it isn't locked for a conviction
+        pub(crate) fn set_vote_conviction(
+            who: &T::AccountId,
+            id: [u8; 32],
+            vote: &VoteChoice<BalanceOf<T>>,
+        ) -> Conviction {
+            if *vote == VoteChoice::Abstain {
+                return Conviction::None;
+            }
+            let conviction = AccountConvictions::<T>::get(who);
+            if conviction != Conviction::None {
+                VoteConvictions::<T>::insert(who, id, conviction);
+            }
+            conviction
+        }
+
+        // Freezes the largest of the account's vote locks, conviction locks and delegation,
+        // or thaws the account if it has none left
+        pub(crate) fn update_vote_freeze(who: &T::AccountId) -> DispatchResult {
+            let delegated = Delegations::<T>::get(who).map_or_else(Zero::zero, |(_, amount)| amount);
+            let frozen = VoteLocksFor::<T>::get(who)
+                .iter()
+                .map(|(_, amount)| *amount)
+                .chain(LockExpiries::<T>::get(who).iter().map(|(_, amount)| *amount))
+                .chain(LegacyVoteLocks::<T>::iter_prefix_values(who))
+                .max()
+                .unwrap_or_else(Zero::zero)
+                .max(delegated);
+
+            if frozen.is_zero() {
+                <<T as Config>::NativeBalance>::thaw(&FreezeReason::Vote.into(), who)
+            } else {
+                <<T as Config>::NativeBalance>::set_freeze(&FreezeReason::Vote.into(), who, frozen)
             }
         }
 
@@ -969,11 +3570,46 @@ pub mod pallet {
             who: T::AccountId,
             id: [u8; 32]
         ) -> DispatchResult {
+            // Commitments that weren't revealed in time forfeit part of their collateral
+            if let Some((_, collateral)) = Commitments::<T>::take(&who, id) {
+                // Take the forfeit out of the collateral before unlocking the rest
+                let forfeit = Self::post_parameters(&id).unrevealed_forfeit * collateral;
+                let amount = Self::forfeit_collateral(&who, forfeit)?;
+
+                Self::remove_vote_lock(&who, id)?;
+
+                Self::deposit_event(Event::CommitmentForfeited {
+                    id,
+                    voter: who,
+                    amount,
+                });
+
+                return Ok(());
+            }
+
             // Remove from Votes and get vote amount
-            let (amount, _direction) = Votes::<T>::take(&who, id);
+            let (amount, vote) = Votes::<T>::take(&who, id);
+            let conviction = VoteConvictions::<T>::take(&who, id);
+            let delegated = DelegatedVotes::<T>::take(&who, id);
+
+            // Remove the vote's lock, freezing the largest of the account's remaining votes
+            // Votes cast with a conviction stay locked for a multiple of the voting period after the post was resolvable,
+            // so the lock doesn't depend on when the vote is unfrozen
+            let parameters = Self::post_parameters(&id);
+            let lock_duration = parameters.voting_period.saturating_mul(conviction.lock_periods().into());
+            let until = Posts::<T>::get(id)
+                .map_or_else(frame_system::Pallet::<T>::block_number, |post| Self::resolves_at(post.voting_until, &parameters))
+                .saturating_add(lock_duration);
+            if until <= frame_system::Pallet::<T>::block_number() {
+                Self::remove_vote_lock(&who, id)?;
+            } else {
+                Self::extend_vote_lock(&who, id, until)?;
+            }
 
-            // Remove freeze
-            <<T as Config>::NativeBalance>::decrease_frozen(&FreezeReason::Vote.into(), &who, amount.clone())?;
+            // Pay out the voter's share if they voted on the winning side, by the weight their vote was tallied with
+            // The balance delegated to them is included, so the payouts add up to the pool's winning votes
+            let tally = Self::vote_tally(&vote, amount, delegated, conviction, parameters.tally_mode);
+            Self::pay_out_voter(&who, id, &tally);
 
             // Decrease vote count or remove if 0
             if let Some(count) = VoteCounts::<T>::get(id) {
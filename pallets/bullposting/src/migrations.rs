@@ -0,0 +1,546 @@
+//! Storage migrations for the bullposting pallet.
+//!
+//! Each migration upgrades the pallet's storage by one [`StorageVersion`], and only runs while the pallet is on the
+//! version it upgrades from, so they can all stay in the runtime (in order) until every live chain has run them.
+//!
+//! The migrations implement [`SteppedMigration`] and are registered with `pallet-migrations`, which steps them over as
+//! many blocks as they need. While they run, blocks only include inherents and the pallet's hooks are paused, and once
+//! they complete `try_state` checks the migrated storage.
+//!
+//! `SteppedMigration` has no `pre_upgrade` or `post_upgrade` of its own, so with the `try-runtime` feature each
+//! migration runs its [`MigrationChecks`] itself, before its first step and after its last one.
+
+use crate::*;
+use frame_support::{
+    migrations::{MigrationId, SteppedMigration, SteppedMigrationError},
+    pallet_prelude::*,
+    sp_runtime::traits::{Saturating, Zero},
+    storage_alias,
+    weights::WeightMeter,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::prelude::vec::Vec;
+#[cfg(feature = "try-runtime")]
+use frame_support::{sp_runtime::TryRuntimeError, storage::unhashed};
+
+/// The pallet's name in the IDs of its stepped migrations.
+const MIGRATIONS_PALLET_ID: [u8; 11] = *b"bullposting";
+
+/// Checks of a migration's storage, run before its first step and after its last one with the `try-runtime` feature.
+pub trait MigrationChecks {
+    /// Captures the state to check the migrated storage against.
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError>;
+
+    /// Checks the migrated storage against the state captured by `pre_upgrade`.
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError>;
+}
+
+/// The number of posts, votes and vote locks in storage, which the migrations check against.
+#[cfg(feature = "try-runtime")]
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct StorageCounts {
+    pub posts: u32,
+    pub votes: u32,
+    pub locks: u32,
+}
+
+#[cfg(feature = "try-runtime")]
+impl StorageCounts {
+    /// Counts the posts, votes and vote locks in storage, without decoding the posts.
+    pub fn get<T: Config>() -> Self {
+        let locks = VoteLocksFor::<T>::iter_values()
+            .map(|locks| locks.len())
+            .sum::<usize>()
+            .saturating_add(LegacyVoteLocks::<T>::iter_keys().count());
+
+        StorageCounts {
+            posts: Posts::<T>::iter_keys().count() as u32,
+            votes: Votes::<T>::iter_keys().count() as u32,
+            locks: locks as u32,
+        }
+    }
+}
+
+// Runs the migration's `pre_upgrade` before its first step, keeping the state it captures until the migration completes
+#[cfg(feature = "try-runtime")]
+fn pre_upgrade<M: SteppedMigration + MigrationChecks>() -> Result<(), SteppedMigrationError> {
+    let state = M::pre_upgrade().map_err(|_| SteppedMigrationError::Failed)?;
+    unhashed::put(&pre_upgrade_key::<M>(), &state);
+    Ok(())
+}
+
+// Runs the migration's `post_upgrade` once it completes, against the state captured by `pre_upgrade`
+#[cfg(feature = "try-runtime")]
+fn post_upgrade<M: SteppedMigration + MigrationChecks>() -> Result<(), SteppedMigrationError> {
+    let state = unhashed::take::<Vec<u8>>(&pre_upgrade_key::<M>()).ok_or(SteppedMigrationError::Failed)?;
+    M::post_upgrade(state).map_err(|_| SteppedMigrationError::Failed)
+}
+
+// The key the state captured by a migration's `pre_upgrade` is kept under between its steps
+#[cfg(feature = "try-runtime")]
+fn pre_upgrade_key<M: SteppedMigration>() -> Vec<u8> {
+    (b":bullposting:pre_upgrade:", M::id()).encode()
+}
+
+/// Steps a migration as many times as `meter` allows, starting the first step from `cursor`.
+///
+/// A migration only starts while the pallet is on storage version `from`, and bumps it to `from + 1` once `step` has
+/// nothing left to migrate.
+fn step_migration<T: Config, M: SteppedMigration + MigrationChecks>(
+    from: u16,
+    mut cursor: Option<M::Cursor>,
+    step_weight: Weight,
+    meter: &mut WeightMeter,
+    step: impl Fn(Option<M::Cursor>) -> Option<M::Cursor>,
+) -> Result<Option<M::Cursor>, SteppedMigrationError> {
+    if cursor.is_none() && Pallet::<T>::on_chain_storage_version() != from {
+        return Ok(None);
+    }
+
+    if meter.remaining().any_lt(step_weight) {
+        return Err(SteppedMigrationError::InsufficientWeight { required: step_weight });
+    }
+
+    #[cfg(feature = "try-runtime")]
+    if cursor.is_none() {
+        pre_upgrade::<M>()?;
+    }
+
+    while meter.try_consume(step_weight).is_ok() {
+        cursor = step(cursor);
+        if cursor.is_none() {
+            StorageVersion::new(from.saturating_add(1)).put::<Pallet<T>>();
+            #[cfg(feature = "try-runtime")]
+            post_upgrade::<M>()?;
+            break;
+        }
+    }
+
+    Ok(cursor)
+}
+
+/// Moves the voters of posts that are still in flight from the old `Voters` lists into [`PostVoters`].
+pub mod v1 {
+    use super::*;
+
+    /// The `MaxVoters` that bounded the old `Voters` lists.
+    pub const OLD_MAX_VOTERS: u32 = 10_000;
+
+    /// The storage layout before the migration.
+    pub mod v0 {
+        use super::*;
+
+        /// The list of voters on each post ID.
+        /// Decoded as a plain `Vec`, which encodes the same as the `BoundedVec` it was stored as,
+        /// so lists are still migrated if `MaxVoters` has been lowered since.
+        #[storage_alias]
+        pub type Voters<T: Config> = StorageMap<
+            Pallet<T>,
+            Blake2_128Concat,
+            [u8; 32],
+            Vec<<T as frame_system::Config>::AccountId>,
+        >;
+    }
+
+    /// Moves every voter in the `Voters` lists into [`PostVoters`], and counts the ones with an unrevealed commitment
+    /// in [`CommitmentCounts`] so they count towards `MaxVoters`.
+    /// Each step moves voters from the end of a list, writing back whatever is left of it, so there's nothing to track
+    /// between steps.
+    pub struct MigrateToPostVoters<T>(PhantomData<T>);
+
+    impl<T: Config> MigrateToPostVoters<T> {
+        /// The weight of reading a `Voters` list and writing back what's left of it.
+        pub fn list_weight() -> Weight {
+            let list_len = (OLD_MAX_VOTERS as u64).saturating_mul(T::AccountId::max_encoded_len() as u64);
+            T::DbWeight::get()
+                .reads_writes(1, 1)
+                .saturating_add(Weight::from_parts(0, list_len))
+        }
+
+        /// The weight of moving a single voter.
+        pub fn voter_weight() -> Weight {
+            T::DbWeight::get().reads_writes(2, 2)
+        }
+    }
+
+    impl<T: Config> SteppedMigration for MigrateToPostVoters<T> {
+        type Cursor = ();
+        type Identifier = MigrationId<11>;
+
+        fn id() -> Self::Identifier {
+            MigrationId { pallet_id: MIGRATIONS_PALLET_ID, version_from: 0, version_to: 1 }
+        }
+
+        fn step(
+            cursor: Option<Self::Cursor>,
+            meter: &mut WeightMeter,
+        ) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+            if cursor.is_none() && Pallet::<T>::on_chain_storage_version() != 0 {
+                return Ok(None);
+            }
+
+            let required = Self::list_weight().saturating_add(Self::voter_weight());
+            if meter.remaining().any_lt(required) {
+                return Err(SteppedMigrationError::InsufficientWeight { required });
+            }
+
+            #[cfg(feature = "try-runtime")]
+            if cursor.is_none() {
+                pre_upgrade::<Self>()?;
+            }
+
+            while meter.try_consume(Self::list_weight()).is_ok() {
+                let Some((id, mut voters)) = v0::Voters::<T>::iter().next() else {
+                    StorageVersion::new(1).put::<Pallet<T>>();
+                    #[cfg(feature = "try-runtime")]
+                    post_upgrade::<Self>()?;
+                    return Ok(None);
+                };
+
+                while meter.try_consume(Self::voter_weight()).is_ok() {
+                    let Some(voter) = voters.pop() else {
+                        break;
+                    };
+
+                    if Commitments::<T>::contains_key(&voter, id) {
+                        CommitmentCounts::<T>::mutate(id, |count| count.saturating_inc());
+                    }
+                    PostVoters::<T>::insert(id, voter, ());
+                }
+
+                if !voters.is_empty() {
+                    v0::Voters::<T>::insert(id, voters);
+                    break;
+                }
+                v0::Voters::<T>::remove(id);
+            }
+
+            Ok(Some(()))
+        }
+    }
+
+    impl<T: Config> MigrationChecks for MigrateToPostVoters<T> {
+        /// Captures the storage counts, the voters there will be in [`PostVoters`], and the unrevealed commitments
+        /// there will be in [`CommitmentCounts`].
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            let mut voters: scale_info::prelude::collections::BTreeSet<_> = PostVoters::<T>::iter_keys().collect();
+            let mut commitments: u32 = CommitmentCounts::<T>::iter_values().sum();
+            for (id, list) in v0::Voters::<T>::iter() {
+                for voter in list {
+                    if Commitments::<T>::contains_key(&voter, id) {
+                        commitments.saturating_inc();
+                    }
+                    voters.insert((id, voter));
+                }
+            }
+
+            Ok((StorageCounts::get::<T>(), voters.len() as u32, commitments).encode())
+        }
+
+        /// Checks that every `Voters` list was moved, and the voters and commitments were counted.
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            let (counts, voters, commitments) = <(StorageCounts, u32, u32)>::decode(&mut &state[..])
+                .map_err(|_| "Couldn't decode the pre-upgrade state")?;
+
+            ensure!(v0::Voters::<T>::iter_keys().next().is_none(), "Voters list left after the migration");
+            ensure!(PostVoters::<T>::iter_keys().count() as u32 == voters, "PostVoters don't match the old Voters lists");
+            ensure!(
+                CommitmentCounts::<T>::iter_values().sum::<u32>() == commitments,
+                "CommitmentCounts don't match the commitments in the old Voters lists"
+            );
+            ensure!(StorageCounts::get::<T>() == counts, "Posts, votes or locks changed by the migration");
+            ensure!(Pallet::<T>::on_chain_storage_version() == 1, "Storage version wasn't bumped to 1");
+
+            Ok(())
+        }
+    }
+}
+
+/// Adds the abstaining votes and the weighted tallies to every [`Post`].
+///
+/// The old `Direction` of a vote encodes the same as the [`VoteChoice`] it maps to, `Bullish` as `Aye`, `Bearish` as `Nay`
+/// and `Tie` as `Abstain`. Tie votes weren't counted in any tally, so they're summed into the abstaining votes of their post.
+pub mod v2 {
+    use super::*;
+
+    /// The storage layout before the migration.
+    pub mod v1 {
+        use super::*;
+
+        /// A post before it had abstaining votes and weighted tallies.
+        #[derive(Encode, Decode)]
+        pub struct Post<AccountId, Balance, BlockNumber> {
+            pub submitter: AccountId,
+            pub bond: Balance,
+            pub bull_votes: Balance,
+            pub bear_votes: Balance,
+            pub voting_until: BlockNumber,
+            pub resolved: bool,
+        }
+
+        #[storage_alias]
+        pub type Posts<T: Config> = StorageMap<
+            Pallet<T>,
+            Blake2_128Concat,
+            [u8; 32],
+            Post<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+        >;
+    }
+
+    /// How far [`MigrateToV2`] has got.
+    #[derive(Debug, PartialEq, Clone, Encode, Decode, MaxEncodedLen)]
+    pub enum Cursor<AccountId> {
+        /// Rewriting posts in the new layout, after the post with this ID.
+        Posts(Option<[u8; 32]>),
+        /// Summing the abstaining votes into their posts, after this account's vote on this post.
+        AbstainVotes(Option<(AccountId, [u8; 32])>),
+    }
+
+    /// Rewrites every post with no abstaining votes, and its raw tallies as its weighted tallies,
+    /// as posts submitted before `TallyMode` were tallied linearly.
+    /// Then sums the amount of every abstaining vote into the abstaining votes of its post.
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> MigrateToV2<T> {
+        /// The weight of migrating a single post or vote.
+        pub fn step_weight() -> Weight {
+            T::DbWeight::get()
+                .reads_writes(2, 1)
+                .saturating_add(Weight::from_parts(0, Post::<T>::max_encoded_len() as u64))
+                .saturating_add(Weight::from_parts(0, <(BalanceOf<T>, VoteChoice<BalanceOf<T>>)>::max_encoded_len() as u64))
+        }
+
+        // Migrates the post or vote after `cursor`, returning its cursor, or `None` once there's nothing left to migrate
+        fn migrate_next(cursor: Cursor<T::AccountId>) -> Option<Cursor<T::AccountId>> {
+            match cursor {
+                Cursor::Posts(last) => {
+                    let mut iter = match last {
+                        Some(id) => v1::Posts::<T>::iter_from(v1::Posts::<T>::hashed_key_for(id)),
+                        None => v1::Posts::<T>::iter(),
+                    };
+
+                    let Some((id, post)) = iter.next() else {
+                        return Some(Cursor::AbstainVotes(None));
+                    };
+                    Posts::<T>::insert(id, Post {
+                        submitter: post.submitter,
+                        bond: post.bond,
+                        bull_votes: post.bull_votes,
+                        bear_votes: post.bear_votes,
+                        abstain_votes: Zero::zero(),
+                        bull_weight: post.bull_votes,
+                        bear_weight: post.bear_votes,
+                        bull_delegated_weight: Zero::zero(),
+                        bear_delegated_weight: Zero::zero(),
+                        voting_until: post.voting_until,
+                        resolved: post.resolved,
+                    });
+
+                    Some(Cursor::Posts(Some(id)))
+                },
+                Cursor::AbstainVotes(last) => {
+                    let mut iter = match last {
+                        Some((who, id)) => Votes::<T>::iter_from(Votes::<T>::hashed_key_for(who, id)),
+                        None => Votes::<T>::iter(),
+                    };
+
+                    let (who, id, (amount, vote)) = iter.next()?;
+                    if vote == VoteChoice::Abstain {
+                        Posts::<T>::mutate_extant(id, |post| post.abstain_votes.saturating_accrue(amount));
+                    }
+
+                    Some(Cursor::AbstainVotes(Some((who, id))))
+                },
+            }
+        }
+    }
+
+    impl<T: Config> SteppedMigration for MigrateToV2<T> {
+        type Cursor = Cursor<T::AccountId>;
+        type Identifier = MigrationId<11>;
+
+        fn id() -> Self::Identifier {
+            MigrationId { pallet_id: MIGRATIONS_PALLET_ID, version_from: 1, version_to: 2 }
+        }
+
+        fn step(
+            cursor: Option<Self::Cursor>,
+            meter: &mut WeightMeter,
+        ) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+            step_migration::<T, Self>(1, cursor, Self::step_weight(), meter, |cursor| {
+                Self::migrate_next(cursor.unwrap_or(Cursor::Posts(None)))
+            })
+        }
+    }
+
+    impl<T: Config> MigrationChecks for MigrateToV2<T> {
+        /// Captures the storage counts, the total bullish and bearish votes on every post, and the total of the
+        /// abstaining votes on posts.
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            let (bull_votes, bear_votes) = v1::Posts::<T>::iter_values().fold(
+                (BalanceOf::<T>::zero(), BalanceOf::<T>::zero()),
+                |(bull, bear), post| (bull.saturating_add(post.bull_votes), bear.saturating_add(post.bear_votes)),
+            );
+            let abstain_votes = Votes::<T>::iter()
+                .filter(|(_, id, (_, vote))| *vote == VoteChoice::Abstain && v1::Posts::<T>::contains_key(id))
+                .fold(BalanceOf::<T>::zero(), |total, (_, _, (amount, _))| total.saturating_add(amount));
+
+            Ok((StorageCounts::get::<T>(), bull_votes, bear_votes, abstain_votes).encode())
+        }
+
+        /// Checks that every post decodes in the new layout with its votes unchanged, its raw votes as its weights,
+        /// and the abstaining votes summed into it.
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            let (counts, bull_votes, bear_votes, abstain_votes) =
+                <(StorageCounts, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>)>::decode(&mut &state[..])
+                    .map_err(|_| "Couldn't decode the pre-upgrade state")?;
+
+            let mut totals = (BalanceOf::<T>::zero(), BalanceOf::<T>::zero(), BalanceOf::<T>::zero());
+            let mut posts: u32 = 0;
+            for post in Posts::<T>::iter_values() {
+                ensure!(
+                    post.bull_weight == post.bull_votes && post.bear_weight == post.bear_votes,
+                    "Post weights don't match its votes"
+                );
+                ensure!(
+                    post.bull_delegated_weight.is_zero() && post.bear_delegated_weight.is_zero(),
+                    "Post has delegated weight"
+                );
+                totals.0.saturating_accrue(post.bull_votes);
+                totals.1.saturating_accrue(post.bear_votes);
+                totals.2.saturating_accrue(post.abstain_votes);
+                posts.saturating_inc();
+            }
+
+            ensure!(posts == counts.posts, "Post doesn't decode after the migration");
+            ensure!(totals == (bull_votes, bear_votes, abstain_votes), "Post votes changed by the migration");
+            ensure!(StorageCounts::get::<T>() == counts, "Posts, votes or locks changed by the migration");
+            ensure!(Pallet::<T>::on_chain_storage_version() == 2, "Storage version wasn't bumped to 2");
+
+            Ok(())
+        }
+    }
+}
+
+/// Locks every vote cast before [`VoteLocksFor`] by its amount, so that unlocking one of an account's votes doesn't
+/// unlock the others.
+///
+/// Votes were always frozen back then, so with `VoteCollateral::Freeze` each account's freeze is then set to the largest
+/// of its locks. An account with more votes than `MaxVotesPerAccount` has the rest locked in [`LegacyVoteLocks`], which
+/// its freeze keeps covering until each of those votes is unfrozen.
+pub mod v3 {
+    use super::*;
+
+    /// Adds a lock to [`VoteLocksFor`] for every vote that has none, or to [`LegacyVoteLocks`] once the account's
+    /// `VoteLocksFor` is full.
+    /// The cursor is the account and post ID of the last vote migrated.
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> MigrateToV3<T> {
+        /// The weight of migrating a single vote, including updating its account's freeze.
+        pub fn step_weight() -> Weight {
+            let locks_len = BoundedVec::<([u8; 32], BalanceOf<T>), T::MaxVotesPerAccount>::max_encoded_len()
+                .saturating_add(BoundedVec::<(BlockNumberFor<T>, BalanceOf<T>), T::MaxVotesPerAccount>::max_encoded_len());
+            T::DbWeight::get()
+                .reads_writes(6, 4)
+                .saturating_add(Weight::from_parts(0, locks_len as u64))
+        }
+
+        // Migrates the vote after `cursor`, returning its cursor, or `None` once there are no votes left to migrate
+        fn migrate_next(cursor: Option<(T::AccountId, [u8; 32])>) -> Option<(T::AccountId, [u8; 32])> {
+            let mut iter = match cursor {
+                Some((who, id)) => Votes::<T>::iter_from(Votes::<T>::hashed_key_for(who, id)),
+                None => Votes::<T>::iter(),
+            };
+
+            let (who, id, (amount, _)) = iter.next()?;
+            let locked = VoteLocksFor::<T>::mutate(&who, |locks| {
+                if locks.iter().any(|(post, _)| *post == id) || LegacyVoteLocks::<T>::contains_key(&who, id) {
+                    return false;
+                }
+                if locks.try_push((id, amount)).is_err() {
+                    LegacyVoteLocks::<T>::insert(&who, id, amount);
+                }
+                true
+            });
+
+            // The old freeze already covers the vote, so this can only lower it to the largest lock
+            if locked && T::VoteCollateral::get() == VoteCollateral::Freeze {
+                let _ = Pallet::<T>::update_vote_freeze(&who);
+            }
+
+            Some((who, id))
+        }
+    }
+
+    impl<T: Config> SteppedMigration for MigrateToV3<T> {
+        type Cursor = (T::AccountId, [u8; 32]);
+        type Identifier = MigrationId<11>;
+
+        fn id() -> Self::Identifier {
+            MigrationId { pallet_id: MIGRATIONS_PALLET_ID, version_from: 2, version_to: 3 }
+        }
+
+        fn step(
+            cursor: Option<Self::Cursor>,
+            meter: &mut WeightMeter,
+        ) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+            step_migration::<T, Self>(2, cursor, Self::step_weight(), meter, Self::migrate_next)
+        }
+    }
+
+    impl<T: Config> MigrationChecks for MigrateToV3<T> {
+        /// Captures the storage counts and the number of votes without a lock.
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            let unlocked = Votes::<T>::iter_keys()
+                .filter(|(who, id)| {
+                    !VoteLocksFor::<T>::get(who).iter().any(|(post, _)| post == id) &&
+                        !LegacyVoteLocks::<T>::contains_key(who, id)
+                })
+                .count() as u32;
+
+            Ok((StorageCounts::get::<T>(), unlocked).encode())
+        }
+
+        /// Checks that every vote is locked, that a lock was added for each vote without one, and that each account's
+        /// freeze still covers its locks.
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            use frame_support::traits::fungible::InspectFreeze;
+
+            let (counts, unlocked) = <(StorageCounts, u32)>::decode(&mut &state[..])
+                .map_err(|_| "Couldn't decode the pre-upgrade state")?;
+
+            for (who, id, (amount, _)) in Votes::<T>::iter() {
+                let locked = VoteLocksFor::<T>::get(&who)
+                    .iter()
+                    .find(|(post, _)| *post == id)
+                    .map(|(_, locked)| *locked)
+                    .or_else(|| LegacyVoteLocks::<T>::get(&who, id));
+                ensure!(locked == Some(amount), "Vote isn't locked by its amount after the migration");
+
+                if T::VoteCollateral::get() == VoteCollateral::Freeze {
+                    ensure!(
+                        <<T as Config>::NativeBalance>::balance_frozen(&FreezeReason::Vote.into(), &who) >= amount,
+                        "Vote freeze doesn't cover the account's votes"
+                    );
+                }
+            }
+
+            ensure!(
+                StorageCounts::get::<T>() == StorageCounts { locks: counts.locks.saturating_add(unlocked), ..counts },
+                "A lock wasn't added for every vote without one"
+            );
+            ensure!(Pallet::<T>::on_chain_storage_version() == 3, "Storage version wasn't bumped to 3");
+
+            Ok(())
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use crate as pallet_parachain_bullposting;
 use frame_support::{
     derive_impl,
+    migrations::MultiStepMigrator,
     parameter_types,
+    weights::Weight,
+    PalletId,
 };
-use sp_runtime::BuildStorage;
+use sp_runtime::{BuildStorage, Percent};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 type Balance = u64;
@@ -40,6 +43,24 @@ mod runtime {
 impl frame_system::Config for Test {
     type Block = Block;
     type AccountData = pallet_balances::AccountData<Balance>;
+    type MultiBlockMigrator = MockedMigrator;
+}
+
+parameter_types! {
+    pub static MigrationsOngoing: bool = false;
+}
+
+// Stands in for `pallet-migrations`, reporting migrations as ongoing while `MigrationsOngoing` is set
+pub struct MockedMigrator;
+
+impl MultiStepMigrator for MockedMigrator {
+    fn ongoing() -> bool {
+        MigrationsOngoing::get()
+    }
+
+    fn step() -> Weight {
+        Weight::zero()
+    }
 }
 
 parameter_types! {
@@ -67,10 +88,28 @@ parameter_types! {
     pub const VotingPeriod: BlockNumber = 1000;
     pub const BondMinimum: u32 = 50;
     pub const VoteMinimum: u32 = 50;
+    pub static QuorumVotes: Balance = 0;
+    pub static QuorumVoters: u32 = 0;
+    pub const WithdrawalFee: Balance = 10;
     pub const MaxVoters: u32 = 2000;
+    pub const MaxVotesPerAccount: u32 = 100;
+    pub static VoteCollateralMode: pallet_parachain_bullposting::VoteCollateral = pallet_parachain_bullposting::VoteCollateral::Freeze;
     pub const StorageRent: u32 = 100;
     pub const MaxInputLength: u32 = 2000;
     pub const UnfreezeLimit: u32 = 1000;
+    pub const MaxExpiringPerBlock: u32 = 100;
+    pub const MaxResolutionsPerBlock: u32 = 2;
+    pub const MaxExpiryBlocksScanned: u32 = 5;
+    pub static VoterPayouts: bool = false;
+    pub static VoterRewardCut: Percent = Percent::zero();
+    pub static CommitReveal: bool = false;
+    pub const RevealPeriod: BlockNumber = 100;
+    pub const UnrevealedForfeit: Percent = Percent::from_percent(50);
+    pub static VoteTallyMode: pallet_parachain_bullposting::TallyMode = pallet_parachain_bullposting::TallyMode::Linear;
+    pub static PotId: Option<PalletId> = None;
+    pub static PotShortfall: pallet_parachain_bullposting::ShortfallBehaviour = pallet_parachain_bullposting::ShortfallBehaviour::PayPartially;
+    pub const ShortfallMintCap: Balance = 100;
+    pub const ShortfallMintPeriod: BlockNumber = 10;
 }
 
 impl pallet_parachain_bullposting::Config for Test {
@@ -80,6 +119,7 @@ impl pallet_parachain_bullposting::Config for Test {
     type RuntimeHoldReason = RuntimeHoldReason;
     type RuntimeFreezeReason = RuntimeFreezeReason;
     type FreezeIdentifier = RuntimeFreezeReason;
+    type AdminOrigin = frame_system::EnsureRoot<u64>;
     type RewardStyle = RewardStyle;
     type FlatReward = FlatReward;
     type RewardCoefficient = RewardCoefficient;
@@ -89,10 +129,30 @@ impl pallet_parachain_bullposting::Config for Test {
     type VotingPeriod = VotingPeriod;
     type BondMinimum = BondMinimum;
     type VoteMinimum = VoteMinimum;
+    type QuorumVotes = QuorumVotes;
+    type QuorumVoters = QuorumVoters;
+    type WithdrawalFee = WithdrawalFee;
     type MaxVoters = MaxVoters;
+    type MaxVotesPerAccount = MaxVotesPerAccount;
+    type VoteCollateral = VoteCollateralMode;
     type StorageRent = StorageRent;
     type MaxInputLength = MaxInputLength;
     type UnfreezeLimit = UnfreezeLimit;
+    type MaxExpiringPerBlock = MaxExpiringPerBlock;
+    type MaxResolutionsPerBlock = MaxResolutionsPerBlock;
+    type MaxExpiryBlocksScanned = MaxExpiryBlocksScanned;
+    type VoterPayouts = VoterPayouts;
+    type VoterRewardCut = VoterRewardCut;
+    type CommitReveal = CommitReveal;
+    type RevealPeriod = RevealPeriod;
+    type UnrevealedForfeit = UnrevealedForfeit;
+    type TallyMode = VoteTallyMode;
+    type RewardPolicy = pallet_parachain_bullposting::StylePolicy;
+    type SlashPolicy = pallet_parachain_bullposting::StylePolicy;
+    type PotId = PotId;
+    type PotShortfall = PotShortfall;
+    type ShortfallMintCap = ShortfallMintCap;
+    type ShortfallMintPeriod = ShortfallMintPeriod;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -104,3 +164,16 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     genesis.assimilate_storage(&mut t).unwrap();
     t.into()
 }
+
+// Build genesis storage, run the test, then check that the pallet's storage is still consistent.
+pub fn build_and_execute(test: impl FnOnce()) {
+    execute_with_try_state(new_test_ext(), test)
+}
+
+// Run the test in the given externalities, then check that the pallet's storage is still consistent.
+pub fn execute_with_try_state(mut ext: sp_io::TestExternalities, test: impl FnOnce()) {
+    ext.execute_with(|| {
+        test();
+        Bullposting::do_try_state().expect("Storage invariants broken after the test");
+    });
+}
@@ -0,0 +1,84 @@
+//! Reward and slash policies used when resolving a post.
+//!
+//! The runtime picks a policy with `Config::RewardPolicy` and `Config::SlashPolicy`. Custom policies
+//! (eg. based on the margin of victory or the turnout) can be written by implementing the traits below.
+
+use crate::{BalanceOf, Config, ParametersOf, Post};
+use frame_support::sp_runtime::{Percent, Permill};
+
+/// Determines the submitter's reward for a post that was resolved as Bullish.
+pub trait RewardPolicy<T: Config> {
+    /// Returns the amount of tokens the submitter is rewarded with, before the voters' cut is taken out.
+    ///
+    /// ## Parameters
+    /// - `post`: The resolved post, including its bond and vote tallies
+    /// - `voter_count`: The number of accounts that voted on the post
+    /// - `parameters`: The parameters the post was submitted under
+    fn reward(post: &Post<T>, voter_count: u32, parameters: &ParametersOf<T>) -> BalanceOf<T>;
+}
+
+/// Determines how much of the submitter's bond is slashed for a post that was resolved as Bearish.
+pub trait SlashPolicy<T: Config> {
+    /// Returns the amount of tokens to slash from the submitter.
+    /// The pallet never slashes more than the post's bond, regardless of the amount returned.
+    ///
+    /// ## Parameters
+    /// - `post`: The resolved post, including its bond and vote tallies
+    /// - `voter_count`: The number of accounts that voted on the post
+    /// - `parameters`: The parameters the post was submitted under
+    fn slash(post: &Post<T>, voter_count: u32, parameters: &ParametersOf<T>) -> BalanceOf<T>;
+}
+
+/// Rewards `flat_reward` and slashes `flat_slash`, independent of the bond.
+pub struct FlatPolicy;
+
+impl<T: Config> RewardPolicy<T> for FlatPolicy {
+    fn reward(_post: &Post<T>, _voter_count: u32, parameters: &ParametersOf<T>) -> BalanceOf<T> {
+        parameters.flat_reward
+    }
+}
+
+impl<T: Config> SlashPolicy<T> for FlatPolicy {
+    fn slash(_post: &Post<T>, _voter_count: u32, parameters: &ParametersOf<T>) -> BalanceOf<T> {
+        parameters.flat_slash
+    }
+}
+
+/// Rewards `reward_coefficient` percent of the bond and slashes `slash_coefficient` percent of the bond.
+pub struct CoefficientPolicy;
+
+impl<T: Config> RewardPolicy<T> for CoefficientPolicy {
+    fn reward(post: &Post<T>, _voter_count: u32, parameters: &ParametersOf<T>) -> BalanceOf<T> {
+        Permill::from_percent(parameters.reward_coefficient) * post.bond
+    }
+}
+
+impl<T: Config> SlashPolicy<T> for CoefficientPolicy {
+    fn slash(post: &Post<T>, _voter_count: u32, parameters: &ParametersOf<T>) -> BalanceOf<T> {
+        // `Percent::from_percent` saturates at 100
+        Percent::from_percent(parameters.slash_coefficient) * post.bond
+    }
+}
+
+/// Picks between `FlatPolicy` and `CoefficientPolicy` with the `reward_style` and `slash_style` parameters.
+/// False == `FlatPolicy`
+/// True == `CoefficientPolicy`
+pub struct StylePolicy;
+
+impl<T: Config> RewardPolicy<T> for StylePolicy {
+    fn reward(post: &Post<T>, voter_count: u32, parameters: &ParametersOf<T>) -> BalanceOf<T> {
+        match parameters.reward_style {
+            false => <FlatPolicy as RewardPolicy<T>>::reward(post, voter_count, parameters),
+            true => <CoefficientPolicy as RewardPolicy<T>>::reward(post, voter_count, parameters),
+        }
+    }
+}
+
+impl<T: Config> SlashPolicy<T> for StylePolicy {
+    fn slash(post: &Post<T>, voter_count: u32, parameters: &ParametersOf<T>) -> BalanceOf<T> {
+        match parameters.slash_style {
+            false => <FlatPolicy as SlashPolicy<T>>::slash(post, voter_count, parameters),
+            true => <CoefficientPolicy as SlashPolicy<T>>::slash(post, voter_count, parameters),
+        }
+    }
+}
@@ -1,12 +1,13 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
-use frame_support::traits::fungible::{Inspect, InspectHold, Mutate, InspectFreeze};
+use crate::{mock::*, Error, Event, WeightInfo};
+use frame_support::{assert_noop, assert_ok, weights::Weight};
+use frame_support::traits::fungible::{Inspect, InspectHold, Mutate, MutateHold, MutateFreeze, InspectFreeze};
 use frame_support::traits::tokens::{Preservation, Fortitude};
+use frame_support::traits::{Get, Hooks};
 
 
 #[test]
 fn test_try_submit_post() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         let alice = 0;
         let bob = 1;
         let bond = 300;
@@ -44,8 +45,13 @@ fn test_try_submit_post() {
             bond,
             bull_votes: 0,
             bear_votes: 0,
+            abstain_votes: 0,
+            bull_weight: 0,
+            bear_weight: 0,
+            bull_delegated_weight: 0,
+            bear_delegated_weight: 0,
             voting_until: System::block_number() + voting_period,
-            ended: false,
+            resolved: false,
         };
         assert_eq!(crate::Posts::<Test>::get(post_id), Some(testpost));
         System::assert_last_event(
@@ -72,7 +78,7 @@ fn test_try_submit_post() {
 
 #[test]
 fn test_try_submit_vote() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         let alice = 0;
         let bob = 1;
         let charlie = 2;
@@ -93,73 +99,73 @@ fn test_try_submit_vote() {
         assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_url.clone(), bond));
 
         // Can't submit an empty post info with your vote
-        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), empty_vote, vote_amount, crate::Direction::Bullish), Error::<Test>::EmptyInput);
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), empty_vote, vote_amount, crate::VoteChoice::Aye), Error::<Test>::EmptyInput);
 
         // Cannot submit a vote lower than `VoteMinimum`
-        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), 25, crate::Direction::Bullish), Error::<Test>::VoteTooLow);
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), 25, crate::VoteChoice::Aye), Error::<Test>::VoteTooLow);
 
         // Can't vote on a post that's longer than `MaxUrlLength`
-        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), too_long, vote_amount, crate::Direction::Bullish), Error::<Test>::InputTooLong);
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), too_long, vote_amount, crate::VoteChoice::Aye), Error::<Test>::InputTooLong);
 
         // Can't vote on a non-existant post
-        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), fake_post_url, vote_amount, crate::Direction::Bullish), Error::<Test>::PostDoesNotExist);
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), fake_post_url, vote_amount, crate::VoteChoice::Aye), Error::<Test>::PostDoesNotExist);
 
         // Can't vote with more than your balance
-        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), 1500, crate::Direction::Bullish), Error::<Test>::InsufficientFreeBalance);
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), 1500, crate::VoteChoice::Aye), Error::<Test>::InsufficientFreeBalance);
 
         // Bob votes Bullish
-        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::Direction::Bullish));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::VoteChoice::Aye));
         // Event
         System::assert_last_event(
             Event::VoteSubmitted { 
                 id: post_id, 
                 voter: bob, 
                 vote_amount,
-                direction: crate::Direction::Bullish,
+                vote: crate::VoteChoice::Aye,
             }.into()
         );
         // Check that storage was updated
-        assert_eq!(crate::Votes::<Test>::contains_key(bob, post_id), true);
+        assert!(crate::Votes::<Test>::contains_key(bob, post_id));
 
         // Charlie votes Bearish
-        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_url.clone(), vote_amount, crate::Direction::Bearish));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_url.clone(), vote_amount, crate::VoteChoice::Nay));
         // Event
         System::assert_last_event(
             Event::VoteSubmitted { 
                 id: post_id, 
                 voter: charlie, 
                 vote_amount,
-                direction: crate::Direction::Bearish,
+                vote: crate::VoteChoice::Nay,
             }.into()
         );
 
         // Can't cast an initial vote if you've already voted
         // Tries to change amount
-        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount + 50, crate::Direction::Bullish), Error::<Test>::AlreadyVoted);
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount + 50, crate::VoteChoice::Aye), Error::<Test>::AlreadyVoted);
         // Tries to change direction
-        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::Direction::Bearish), Error::<Test>::AlreadyVoted);
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::VoteChoice::Nay), Error::<Test>::AlreadyVoted);
 
         // Alice votes Bullish
-        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(alice), post_url.clone(), vote_amount, crate::Direction::Bullish));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(alice), post_url.clone(), vote_amount, crate::VoteChoice::Aye));
 
         // Vote on post (starts at 2 because alice and bob are 0 and 1)
         for i in 2..2000u64 {
             Balances::set_balance(&i, vote_amount + 50);
-            let _ = Bullposting::try_submit_vote(RuntimeOrigin::signed(i), post_url.clone(), vote_amount, crate::Direction::Bullish);
+            let _ = Bullposting::try_submit_vote(RuntimeOrigin::signed(i), post_url.clone(), vote_amount, crate::VoteChoice::Aye);
         }
 
         // Can't vote if the maximum number of voters have already voted on this post
-        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(david), post_url.clone(), vote_amount, crate::Direction::Bearish), Error::<Test>::VotersMaxed);
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(david), post_url.clone(), vote_amount, crate::VoteChoice::Nay), Error::<Test>::VotersMaxed);
 
         // Can't vote if the voting period has ended
         System::set_block_number(voting_period + 1);
-        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(david), post_url, vote_amount, crate::Direction::Bullish), Error::<Test>::VotingEnded);
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(david), post_url, vote_amount, crate::VoteChoice::Aye), Error::<Test>::VotingEnded);
     });
 }
 
 #[test]
 fn test_try_update_vote() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         let alice = 0;
         let bob = 1;
         let charlie = 2;
@@ -180,50 +186,50 @@ fn test_try_update_vote() {
         assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_url.clone(), bond));
 
         // Cannot update a vote for a post that is too long
-        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), too_long, vote_amount, crate::Direction::Bullish), Error::<Test>::InputTooLong);
+        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), too_long, vote_amount, crate::VoteChoice::Aye), Error::<Test>::InputTooLong);
 
         // Cannot update a vote without an initial vote
-        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::Direction::Bullish), Error::<Test>::VoteDoesNotExist);
+        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::VoteChoice::Aye), Error::<Test>::VoteDoesNotExist);
 
         // Vote Bullish
-        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::Direction::Bullish));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::VoteChoice::Aye));
         // Event
         System::assert_last_event(
             Event::VoteSubmitted { 
                 id: post_id, 
                 voter: bob, 
                 vote_amount,
-                direction: crate::Direction::Bullish,
+                vote: crate::VoteChoice::Aye,
             }.into()
         );
         // Check that storage was updated
-        assert_eq!(crate::Votes::<Test>::contains_key(bob, post_id), true);
+        assert!(crate::Votes::<Test>::contains_key(bob, post_id));
         let initial = crate::Votes::<Test>::get(bob, post_id);
 
         // Can't submit an empty post info with your vote
-        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), empty_vote, vote_amount, crate::Direction::Bullish), Error::<Test>::EmptyInput);
+        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), empty_vote, vote_amount, crate::VoteChoice::Aye), Error::<Test>::EmptyInput);
 
         // Can't update vote to be below the vote minimum
-        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url.clone(), 25, crate::Direction::Bullish), Error::<Test>::VoteTooLow);
+        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url.clone(), 25, crate::VoteChoice::Aye), Error::<Test>::VoteTooLow);
 
         // Can't vote on a non-existant post
-        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), fake_post_url, vote_amount, crate::Direction::Bullish), Error::<Test>::PostDoesNotExist);
+        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), fake_post_url, vote_amount, crate::VoteChoice::Aye), Error::<Test>::PostDoesNotExist);
 
         // Can't vote with more than your balance
-        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url.clone(), 1500, crate::Direction::Bullish), Error::<Test>::InsufficientFreeBalance);
+        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url.clone(), 1500, crate::VoteChoice::Aye), Error::<Test>::InsufficientFreeBalance);
 
         // Someone else cannot update your vote
-        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(charlie), post_url.clone(), vote_amount, crate::Direction::Bullish), Error::<Test>::VoteDoesNotExist);
+        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(charlie), post_url.clone(), vote_amount, crate::VoteChoice::Aye), Error::<Test>::VoteDoesNotExist);
         
         // Successful vote update to Bearish with higher vote
-        assert_ok!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url.clone(), new_vote_amount, crate::Direction::Bearish));
+        assert_ok!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url.clone(), new_vote_amount, crate::VoteChoice::Nay));
         // Event
         System::assert_last_event(
             Event::VoteUpdated { 
                 id: post_id, 
                 voter: bob, 
                 vote_amount: new_vote_amount,
-                direction: crate::Direction::Bearish,
+                vote: crate::VoteChoice::Nay,
             }.into()
         );
         // Check that storage was updated
@@ -231,14 +237,14 @@ fn test_try_update_vote() {
         assert_ne!(initial, new);
 
         // Successful vote update to Bearish with higher vote
-        assert_ok!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url.clone(), new_vote_amount - 100, crate::Direction::Bullish));
+        assert_ok!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url.clone(), new_vote_amount - 100, crate::VoteChoice::Aye));
         // Event
         System::assert_last_event(
             Event::VoteUpdated { 
                 id: post_id, 
                 voter: bob, 
                 vote_amount: new_vote_amount - 100,
-                direction: crate::Direction::Bullish,
+                vote: crate::VoteChoice::Aye,
             }.into()
         );
         // Check that storage was updated
@@ -247,14 +253,14 @@ fn test_try_update_vote() {
 
         // Can't vote is the voting period has ended
         System::set_block_number(voting_period + 1);
-        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url, vote_amount, crate::Direction::Bullish), Error::<Test>::VotingEnded);
+        assert_noop!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_url, vote_amount, crate::VoteChoice::Aye), Error::<Test>::VotingEnded);
 
     });
 }
 
 #[test]
 fn test_try_resolve_voting() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         let alice = 0;
         let bob = 1;
         let charlie = 2;
@@ -277,8 +283,8 @@ fn test_try_resolve_voting() {
         assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_2_url.clone(), bond));
 
         // Vote
-        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::Direction::Bullish));
-        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_2_url.clone(), vote_amount, crate::Direction::Bearish));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_2_url.clone(), vote_amount, crate::VoteChoice::Nay));
 
 
         // Cannot end during the voting period
@@ -301,22 +307,26 @@ fn test_try_resolve_voting() {
         // Switch which of the below events is commented out and change `pub const RewardStyle: bool` in mock.rs
         // Rewarded event with RewardStyle = false (FlatReward)
         // System::assert_last_event(
-        //     Event::PostEnded { 
+        //     Event::VotingResolved { 
         //         id: post_id, 
         //         submitter: alice, 
-        //         result: crate::Direction::Bullish,
+        //         result: crate::Outcome::Bullish,
         //         rewarded: 300,
         //         slashed: 0,
+        //         turnout: vote_amount,
+        //         voter_count: 1,
         //     }.into()
         // );
         // Rewarded event with RewardStyle = true (RewardCoefficient)
         System::assert_last_event(
-            Event::PostEnded { 
+            Event::VotingResolved { 
                 id: post_id, 
                 submitter: alice, 
-                result: crate::Direction::Bullish,
+                result: crate::Outcome::Bullish,
                 rewarded: bond,
                 slashed: 0,
+                turnout: vote_amount,
+                voter_count: 1,
             }.into()
         );
 
@@ -328,22 +338,26 @@ fn test_try_resolve_voting() {
         // Switch which of the below events is commented out and change `pub const SlashStyle: bool` in mock.rs
         // Slashed event with SlashStyle = false (FlatSlash)
         // System::assert_last_event(
-        //     Event::PostEnded { 
+        //     Event::VotingResolved { 
         //         id: post_2_id, 
         //         submitter: alice, 
-        //         result: crate::Direction::Bearish,
+        //         result: crate::Outcome::Bearish,
         //         rewarded: 0,
         //         slashed: 300,
+        //         turnout: vote_amount,
+        //         voter_count: 1,
         //     }.into()
         // );
         // Slashed event with SlashStyle = true (SlashCoefficient)
         System::assert_last_event(
-            Event::PostEnded { 
+            Event::VotingResolved { 
                 id: post_2_id, 
                 submitter: alice, 
-                result: crate::Direction::Bearish,
+                result: crate::Outcome::Bearish,
                 rewarded: 0,
                 slashed: bond,
+                turnout: vote_amount,
+                voter_count: 1,
             }.into()
         );
     });
@@ -351,7 +365,7 @@ fn test_try_resolve_voting() {
 
 #[test]
 fn try_end_post() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         let alice = 0;
         let bob = 1;
         let charlie = 2;
@@ -370,8 +384,8 @@ fn try_end_post() {
         // Submit post
         assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_url.clone(), bond));
         // Vote on post
-        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::Direction::Bullish));
-        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_url.clone(), vote_amount, crate::Direction::Bearish));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_url.clone(), vote_amount, crate::VoteChoice::Nay));
 
         // Error if the post is not yet ended
         assert_noop!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_url.clone()), Error::<Test>::VotingUnresolved);
@@ -393,13 +407,13 @@ fn try_end_post() {
         assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_url.clone()));
         // Event
         System::assert_last_event(
-            Event::PostResolved { 
+            Event::PostEnded { 
                 id: post_id,
             }.into()
         );
 
         // Check everything was removed from storage
-        assert!(!crate::Voters::<Test>::contains_key(post_id));
+        assert!(crate::PostVoters::<Test>::iter_key_prefix(post_id).next().is_none());
         assert!(!crate::Votes::<Test>::contains_key(charlie, post_id));
         assert!(!crate::Posts::<Test>::contains_key(post_id));
         assert!(!crate::VoteCounts::<Test>::contains_key(post_id));
@@ -408,7 +422,7 @@ fn try_end_post() {
 
 #[test]
 fn test_try_end_post_big() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         let alice = 0;
         let bob = 1;
         let charlie = 2;
@@ -429,10 +443,10 @@ fn test_try_end_post_big() {
         // Vote on post
         for i in 10..1500u64 {
             Balances::set_balance(&i, vote_amount + 50);
-            let _ = Bullposting::try_submit_vote(RuntimeOrigin::signed(i), post_url.clone(), vote_amount, crate::Direction::Bullish);
+            let _ = Bullposting::try_submit_vote(RuntimeOrigin::signed(i), post_url.clone(), vote_amount, crate::VoteChoice::Aye);
         }
-        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::Direction::Bullish));
-        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_url.clone(), vote_amount, crate::Direction::Bearish));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_url.clone(), vote_amount, crate::VoteChoice::Nay));
 
         // Error if the post is not yet ended
         assert_noop!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_url.clone()), Error::<Test>::VotingUnresolved);
@@ -454,7 +468,7 @@ fn test_try_end_post_big() {
         assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_url.clone()));
         // Event
         System::assert_last_event(
-            Event::PartiallyResolved { 
+            Event::PostPartiallyEnded { 
                 id: post_id,
             }.into()
         );
@@ -463,15 +477,1836 @@ fn test_try_end_post_big() {
         assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_url.clone()));
         // Event
         System::assert_last_event(
-            Event::PostResolved { 
+            Event::PostEnded { 
+                id: post_id,
+            }.into()
+        );
+
+        // Check everything was removed from storage
+        assert!(crate::PostVoters::<Test>::iter_key_prefix(post_id).next().is_none());
+        assert!(!crate::Votes::<Test>::contains_key(charlie, post_id));
+        assert!(!crate::Posts::<Test>::contains_key(post_id));
+        assert!(!crate::VoteCounts::<Test>::contains_key(post_id));
+    });
+}
+
+#[test]
+fn test_automatic_resolution() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let bond = 300;
+        let vote_amount = 50;
+        let voting_period = 1000;
+        let post_1: Vec<u8> = "first post".into();
+        let post_1_id = sp_io::hashing::blake2_256(&post_1);
+        let post_2: Vec<u8> = "second post".into();
+        let post_2_id = sp_io::hashing::blake2_256(&post_2);
+        let post_3: Vec<u8> = "third post".into();
+        let post_3_id = sp_io::hashing::blake2_256(&post_3);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        // Submit posts that all end on the same block
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_1.clone(), bond));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(bob), post_2.clone(), bond));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(charlie), post_3.clone(), bond));
+        assert_eq!(crate::ExpiringPosts::<Test>::get(1 + voting_period).len(), 3);
+        assert_eq!(crate::ExpiryCursor::<Test>::get(), Some(1 + voting_period));
+
+        // Vote on a post
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_1.clone(), vote_amount, crate::VoteChoice::Aye));
+
+        // Nothing is resolved while voting is ongoing
+        System::set_block_number(voting_period);
+        Bullposting::on_initialize(voting_period);
+        assert!(!crate::Posts::<Test>::get(post_1_id).unwrap().resolved);
+        assert_eq!(crate::ExpiryCursor::<Test>::get(), Some(1 + voting_period));
+
+        // Only `MaxResolutionsPerBlock` (2 in the mock) posts are resolved in the first block
+        System::set_block_number(voting_period + 1);
+        Bullposting::on_initialize(voting_period + 1);
+        let resolved = [post_1_id, post_2_id, post_3_id].iter()
+            .filter(|id| crate::Posts::<Test>::get(id).unwrap().resolved)
+            .count();
+        assert_eq!(resolved, 2);
+        assert_eq!(crate::ExpiringPosts::<Test>::get(1 + voting_period).len(), 1);
+        assert_eq!(crate::ExpiryCursor::<Test>::get(), Some(1 + voting_period));
+
+        // The remaining post is carried over to the next block
+        System::set_block_number(voting_period + 2);
+        Bullposting::on_initialize(voting_period + 2);
+        assert!(crate::Posts::<Test>::get(post_1_id).unwrap().resolved);
+        assert!(crate::Posts::<Test>::get(post_2_id).unwrap().resolved);
+        assert!(crate::Posts::<Test>::get(post_3_id).unwrap().resolved);
+        assert!(!crate::ExpiringPosts::<Test>::contains_key(1 + voting_period));
+        assert_eq!(crate::ExpiryCursor::<Test>::get(), Some(voting_period + 3));
+
+        // Resolved posts can no longer be resolved manually, but can still be ended
+        assert_noop!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(alice), post_1.clone()), Error::<Test>::PostAlreadyEnded);
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(alice), post_1));
+
+        // After skipped blocks, only `MaxExpiryBlocksScanned` (5 in the mock) blocks of the queue are scanned per block
+        System::set_block_number(voting_period + 100);
+        Bullposting::on_initialize(voting_period + 100);
+        assert_eq!(crate::ExpiryCursor::<Test>::get(), Some(voting_period + 8));
+        Bullposting::on_initialize(voting_period + 101);
+        assert_eq!(crate::ExpiryCursor::<Test>::get(), Some(voting_period + 13));
+    });
+}
+
+#[test]
+fn test_failed_automatic_resolution() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let bond = 300;
+        let vote_amount = 50;
+        let voting_period = 1000;
+        let post: Vec<u8> = "unpayable post".into();
+        let post_id = sp_io::hashing::blake2_256(&post);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), vote_amount, crate::VoteChoice::Aye));
+
+        // The submitter's reward can't be minted without overflowing the total issuance
+        let issuance = pallet_balances::TotalIssuance::<Test>::get();
+        pallet_balances::TotalIssuance::<Test>::put(u64::MAX);
+
+        // The failed resolution is rolled back and reported, and the post is taken off the queue
+        System::set_block_number(voting_period + 1);
+        Bullposting::on_initialize(voting_period + 1);
+        System::assert_last_event(
+            Event::ResolutionFailed {
+                id: post_id,
+                error: sp_runtime::ArithmeticError::Overflow.into(),
+            }.into()
+        );
+        assert!(!crate::Posts::<Test>::get(post_id).unwrap().resolved);
+        assert_eq!(Balances::balance_on_hold(&crate::HoldReason::PostBond.into(), &alice), bond);
+        assert!(!crate::ExpiringPosts::<Test>::contains_key(1 + voting_period));
+        assert!(!crate::PostsToEnd::<Test>::contains_key(post_id));
+
+        // It isn't retried automatically in later blocks
+        System::reset_events();
+        Bullposting::on_initialize(voting_period + 2);
+        assert!(System::events().is_empty());
+
+        // Once the reward can be paid, the post can be resolved manually
+        pallet_balances::TotalIssuance::<Test>::put(issuance);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post));
+        assert!(crate::Posts::<Test>::get(post_id).unwrap().resolved);
+        assert!(crate::PostsToEnd::<Test>::contains_key(post_id));
+    });
+}
+
+#[test]
+fn test_on_idle_end_post() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let dave = 3;
+        let bond = 300;
+        let vote_amount = 500;
+        let voting_period = 1000;
+        let post_url: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/sp_test_primitives/type.BlockNumber.html".into();
+        let post_id = sp_io::hashing::blake2_256(&post_url);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        // Submit post and vote on it
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_url.clone(), bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_url.clone(), vote_amount, crate::VoteChoice::Nay));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(dave), post_url.clone(), vote_amount, crate::VoteChoice::Nay));
+
+        // Unresolved posts are not touched
+        Bullposting::on_idle(2, Weight::MAX);
+        assert_eq!(crate::VoteCounts::<Test>::get(post_id), Some(3));
+
+        // Resolve the post, queueing it to be ended
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_url.clone()));
+        assert!(crate::PostsToEnd::<Test>::contains_key(post_id));
+
+        // Only enough weight is left over to unfreeze two votes
+        let remaining_weight = <() as WeightInfo>::on_idle_end_post(2)
+            .saturating_add(<<Test as frame_system::Config>::DbWeight as Get<frame_support::weights::RuntimeDbWeight>>::get().reads(2));
+        Bullposting::on_idle(voting_period + 1, remaining_weight);
+        assert_eq!(crate::VoteCounts::<Test>::get(post_id), Some(1));
+        assert!(crate::Posts::<Test>::contains_key(post_id));
+        assert!(crate::PostsToEnd::<Test>::contains_key(post_id));
+
+        // Not enough weight left over to do anything
+        Bullposting::on_idle(voting_period + 2, Weight::zero());
+        assert_eq!(crate::VoteCounts::<Test>::get(post_id), Some(1));
+
+        // The rest of the post is ended in a later block
+        Bullposting::on_idle(voting_period + 3, Weight::MAX);
+        System::assert_last_event(
+            Event::PostEnded {
                 id: post_id,
             }.into()
         );
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), 0);
+        assert_eq!(Balances::total_balance_on_hold(&alice), 0);
 
         // Check everything was removed from storage
-        assert!(!crate::Voters::<Test>::contains_key(post_id));
+        assert!(crate::PostVoters::<Test>::iter_key_prefix(post_id).next().is_none());
         assert!(!crate::Votes::<Test>::contains_key(charlie, post_id));
         assert!(!crate::Posts::<Test>::contains_key(post_id));
         assert!(!crate::VoteCounts::<Test>::contains_key(post_id));
+        assert!(!crate::PostsToEnd::<Test>::contains_key(post_id));
+
+        // Ended posts can no longer be ended manually
+        assert_noop!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_url), Error::<Test>::PostDoesNotExist);
+    });
+}
+
+#[test]
+fn test_failed_on_idle_end_post() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let bond = 300;
+        let vote_amount = 500;
+        let voting_period = 1000;
+        let post: Vec<u8> = "unendable post".into();
+        let post_id = sp_io::hashing::blake2_256(&post);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), vote_amount, crate::VoteChoice::Aye));
+
+        // Queue the post to be ended before it's resolved, so ending it fails
+        crate::PostsToEnd::<Test>::insert(post_id, ());
+        Bullposting::on_idle(2, Weight::MAX);
+        System::assert_last_event(
+            Event::EndPostFailed {
+                id: post_id,
+                error: Error::<Test>::VotingUnresolved.into(),
+            }.into()
+        );
+
+        // The post is dropped from the queue, and its vote stays locked
+        assert!(!crate::PostsToEnd::<Test>::contains_key(post_id));
+        assert!(crate::Posts::<Test>::contains_key(post_id));
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), vote_amount);
+
+        // It can still be ended manually
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post.clone()));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post));
+        assert!(!crate::Posts::<Test>::contains_key(post_id));
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), 0);
+    });
+}
+
+#[test]
+fn test_calls_by_id() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let bond = 300;
+        let vote_amount = 500;
+        let voting_period = 1000;
+        let post_url: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/sp_test_primitives/type.BlockNumber.html".into();
+        let post_id = sp_io::hashing::blake2_256(&post_url);
+        let fake_post_id = sp_io::hashing::blake2_256(b"get rekt kid");
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        // Submit post
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_url.clone(), bond));
+
+        // Can't vote on a non-existant post
+        assert_noop!(Bullposting::try_submit_vote_by_id(RuntimeOrigin::signed(bob), fake_post_id, vote_amount, crate::VoteChoice::Aye), Error::<Test>::PostDoesNotExist);
+
+        // Cannot submit a vote lower than `VoteMinimum`
+        assert_noop!(Bullposting::try_submit_vote_by_id(RuntimeOrigin::signed(bob), post_id, 25, crate::VoteChoice::Aye), Error::<Test>::VoteTooLow);
+
+        // Vote by ID
+        assert_ok!(Bullposting::try_submit_vote_by_id(RuntimeOrigin::signed(bob), post_id, vote_amount, crate::VoteChoice::Aye));
+        System::assert_last_event(
+            Event::VoteSubmitted {
+                id: post_id,
+                voter: bob,
+                vote_amount,
+                vote: crate::VoteChoice::Aye,
+            }.into()
+        );
+
+        // Votes by ID and by input text are the same vote
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::VoteChoice::Aye), Error::<Test>::AlreadyVoted);
+        assert_noop!(Bullposting::try_update_vote_by_id(RuntimeOrigin::signed(charlie), post_id, vote_amount, crate::VoteChoice::Nay), Error::<Test>::VoteDoesNotExist);
+
+        // Update vote by ID
+        assert_ok!(Bullposting::try_update_vote_by_id(RuntimeOrigin::signed(bob), post_id, vote_amount - 100, crate::VoteChoice::Nay));
+        System::assert_last_event(
+            Event::VoteUpdated {
+                id: post_id,
+                voter: bob,
+                vote_amount: vote_amount - 100,
+                vote: crate::VoteChoice::Nay,
+            }.into()
+        );
+        assert_eq!(crate::Posts::<Test>::get(post_id).unwrap().bear_votes, vote_amount - 100);
+
+        // Cannot resolve or end during the voting period
+        assert_noop!(Bullposting::try_resolve_voting_by_id(RuntimeOrigin::signed(charlie), post_id), Error::<Test>::VotingStillOngoing);
+        assert_noop!(Bullposting::try_end_post_by_id(RuntimeOrigin::signed(charlie), post_id), Error::<Test>::VotingUnresolved);
+
+        // Resolve and end by ID
+        System::set_block_number(voting_period + 1);
+        assert_noop!(Bullposting::try_resolve_voting_by_id(RuntimeOrigin::signed(charlie), fake_post_id), Error::<Test>::PostDoesNotExist);
+        assert_ok!(Bullposting::try_resolve_voting_by_id(RuntimeOrigin::signed(charlie), post_id));
+        assert!(crate::Posts::<Test>::get(post_id).unwrap().resolved);
+        assert_ok!(Bullposting::try_end_post_by_id(RuntimeOrigin::signed(charlie), post_id));
+        System::assert_last_event(
+            Event::PostEnded {
+                id: post_id,
+            }.into()
+        );
+        assert!(!crate::Posts::<Test>::contains_key(post_id));
+    });
+}
+
+#[test]
+fn test_voter_payouts() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let dave = 3;
+        let bond = 300;
+        let balance = 1001;
+        let voting_period = 1000;
+        let post_1: Vec<u8> = "bearish post".into();
+        let post_1_id = sp_io::hashing::blake2_256(&post_1);
+        let post_2: Vec<u8> = "bullish post".into();
+        let post_2_id = sp_io::hashing::blake2_256(&post_2);
+
+        // Voter payouts are off by default
+        VoterPayouts::set(true);
+        // 20% of a Bullish post's reward goes to the voters
+        VoterRewardCut::set(sp_runtime::Percent::from_percent(20));
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        // Submit posts
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_1.clone(), bond));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_2.clone(), bond));
+
+        // Vote
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_1.clone(), 600, crate::VoteChoice::Nay));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_1.clone(), 200, crate::VoteChoice::Nay));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(dave), post_1.clone(), 100, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(dave), post_2.clone(), 500, crate::VoteChoice::Aye));
+
+        // Resolve voting
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_1.clone()));
+        System::assert_has_event(
+            Event::PayoutPoolCreated {
+                id: post_1_id,
+                direction: crate::Outcome::Bearish,
+                amount: bond,
+            }.into()
+        );
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_2.clone()));
+        // The submitter only gets 80% of the reward
+        System::assert_has_event(
+            Event::VotingResolved {
+                id: post_2_id,
+                submitter: alice,
+                result: crate::Outcome::Bullish,
+                rewarded: 240,
+                slashed: 0,
+                turnout: 500,
+                voter_count: 1,
+            }.into()
+        );
+        assert_eq!(crate::PayoutPools::<Test>::get(post_2_id).unwrap().total, 60);
+
+        // Nothing is paid out until the votes are unfrozen
+        assert_eq!(Balances::free_balance(bob), balance);
+
+        // End posts, paying out the winning voters pro-rata to their vote
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_1));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_2));
+        System::assert_has_event(
+            Event::VoterPaidOut {
+                id: post_1_id,
+                voter: bob,
+                amount: 225,
+            }.into()
+        );
+        assert_eq!(Balances::free_balance(bob), balance + 225);
+        assert_eq!(Balances::free_balance(charlie), balance + 75);
+        // Dave lost on the first post but won the second one
+        assert_eq!(Balances::free_balance(dave), balance + 60);
+        assert_eq!(Balances::free_balance(alice), balance - bond + 240);
+
+        // Pools are removed once the posts are ended
+        assert!(!crate::PayoutPools::<Test>::contains_key(post_1_id));
+        assert!(!crate::PayoutPools::<Test>::contains_key(post_2_id));
+    });
+}
+
+#[test]
+fn test_delegated_voter_payouts() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let dave = 3;
+        let eve = 10000;
+        let bond = 300;
+        let balance = 1001;
+        let voting_period = 1000;
+        let post: Vec<u8> = "bearish post".into();
+        let post_id = sp_io::hashing::blake2_256(&post);
+
+        VoterPayouts::set(true);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+
+        // Eve's delegation counts towards Charlie's vote
+        assert_ok!(Bullposting::delegate(RuntimeOrigin::signed(eve), charlie, 300));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), 600, crate::VoteChoice::Nay));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post.clone(), 100, crate::VoteChoice::Nay));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(dave), post.clone(), 100, crate::VoteChoice::Aye));
+
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post.clone()));
+        // The delegated balance decides the result, but isn't counted in the winning votes the pool is shared by
+        assert_eq!(crate::Posts::<Test>::get(post_id).unwrap().bear_delegated_weight, 300);
+        assert_eq!(crate::PayoutPools::<Test>::get(post_id).unwrap().winning_votes, 700);
+
+        // Charlie is only paid for their own vote, so the delegated balance doesn't earn them Eve's share
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post));
+        assert_eq!(Balances::free_balance(bob), balance + 257);
+        assert_eq!(Balances::free_balance(charlie), balance + 43);
+        assert_eq!(Balances::free_balance(eve), balance);
+        System::assert_has_event(Event::VoterPaidOut { id: post_id, voter: charlie, amount: 43 }.into());
+    });
+}
+
+#[test]
+fn test_set_parameters() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let post_1: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/sp_test_primitives/type.BlockNumber.html".into();
+        let post_1_id = sp_io::hashing::blake2_256(&post_1);
+        let post_2: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/polkadot_sdk_docs/index.html".into();
+        let post_2_id = sp_io::hashing::blake2_256(&post_2);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        // Defaults come from the runtime constants
+        let old_parameters = crate::CurrentParameters::<Test>::get();
+        assert_eq!(old_parameters.voting_period, 1000);
+        assert_eq!(old_parameters.bond_minimum, 50);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_1.clone(), 100));
+
+        let mut new_parameters = old_parameters.clone();
+        new_parameters.voting_period = 10;
+        new_parameters.bond_minimum = 200;
+        new_parameters.storage_rent = 50;
+
+        // Only `AdminOrigin` can set the parameters
+        assert_noop!(Bullposting::set_parameters(RuntimeOrigin::signed(alice), new_parameters.clone()), sp_runtime::DispatchError::BadOrigin);
+
+        // Parameters must be in bounds
+        let mut bad_parameters = new_parameters.clone();
+        bad_parameters.reward_coefficient = 101;
+        assert_noop!(Bullposting::set_parameters(RuntimeOrigin::root(), bad_parameters), Error::<Test>::InvalidParameters);
+        let mut bad_parameters = new_parameters.clone();
+        bad_parameters.slash_coefficient = 101;
+        assert_noop!(Bullposting::set_parameters(RuntimeOrigin::root(), bad_parameters), Error::<Test>::InvalidParameters);
+        let mut bad_parameters = new_parameters.clone();
+        bad_parameters.voting_period = 0;
+        assert_noop!(Bullposting::set_parameters(RuntimeOrigin::root(), bad_parameters), Error::<Test>::InvalidParameters);
+
+        assert_ok!(Bullposting::set_parameters(RuntimeOrigin::root(), new_parameters.clone()));
+        System::assert_last_event(
+            Event::ParametersUpdated {
+                parameters: new_parameters.clone(),
+            }.into()
+        );
+        assert_eq!(crate::CurrentParameters::<Test>::get(), new_parameters);
+
+        // New posts use the new parameters
+        assert_noop!(Bullposting::try_submit_post(RuntimeOrigin::signed(bob), post_2.clone(), 100), Error::<Test>::BondTooLow);
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(bob), post_2.clone(), 200));
+        assert_eq!(crate::Posts::<Test>::get(post_2_id).unwrap().voting_until, 11);
+        assert_eq!(Balances::balance_on_hold(&crate::HoldReason::StorageRent.into(), &bob), 50);
+
+        // The shorter voting period is resolved automatically, even though it was queued after a longer one
+        assert_eq!(crate::ExpiryCursor::<Test>::get(), Some(11));
+        System::set_block_number(11);
+        Bullposting::on_initialize(11);
+        assert!(crate::Posts::<Test>::get(post_2_id).unwrap().resolved);
+        assert_eq!(crate::ExpiryCursor::<Test>::get(), Some(12));
+
+        // Posts in flight keep the parameters they were submitted under
+        assert_eq!(crate::Posts::<Test>::get(post_1_id).unwrap().voting_until, 1001);
+        assert_eq!(crate::PostParameters::<Test>::get(post_1_id), Some(old_parameters));
+        assert_eq!(Balances::balance_on_hold(&crate::HoldReason::StorageRent.into(), &alice), 100);
+
+        // The post's own storage rent is released when it ends
+        System::set_block_number(1002);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_1.clone()));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_1));
+        assert_eq!(Balances::balance_on_hold(&crate::HoldReason::StorageRent.into(), &alice), 0);
+        assert!(!crate::PostParameters::<Test>::contains_key(post_1_id));
+    });
+}
+
+#[test]
+fn test_policies() {
+    use crate::{CoefficientPolicy, FlatPolicy, RewardPolicy, SlashPolicy, StylePolicy};
+
+    build_and_execute(|| {
+        let mut parameters = crate::CurrentParameters::<Test>::get();
+        parameters.flat_reward = 70;
+        parameters.flat_slash = 400;
+        parameters.reward_coefficient = 50;
+        parameters.slash_coefficient = 25;
+        let post = crate::Post::<Test> {
+            submitter: 0,
+            bond: 300,
+            bull_votes: 500,
+            bear_votes: 200,
+            abstain_votes: 0,
+            bull_weight: 500,
+            bear_weight: 200,
+            bull_delegated_weight: 0,
+            bear_delegated_weight: 0,
+            voting_until: 1001,
+            resolved: true,
+        };
+
+        // Flat amounts are independent of the bond
+        assert_eq!(<FlatPolicy as RewardPolicy<Test>>::reward(&post, 2, &parameters), 70);
+        assert_eq!(<FlatPolicy as SlashPolicy<Test>>::slash(&post, 2, &parameters), 400);
+
+        // Coefficients are a percentage of the bond
+        assert_eq!(<CoefficientPolicy as RewardPolicy<Test>>::reward(&post, 2, &parameters), 150);
+        assert_eq!(<CoefficientPolicy as SlashPolicy<Test>>::slash(&post, 2, &parameters), 75);
+
+        // The style parameters pick between the two
+        parameters.reward_style = false;
+        parameters.slash_style = true;
+        assert_eq!(<StylePolicy as RewardPolicy<Test>>::reward(&post, 2, &parameters), 70);
+        assert_eq!(<StylePolicy as SlashPolicy<Test>>::slash(&post, 2, &parameters), 75);
+    });
+}
+
+#[test]
+fn test_pot() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let dave = 3;
+        let balance = 1001;
+        let post_1: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/sp_test_primitives/type.BlockNumber.html".into();
+        let post_1_id = sp_io::hashing::blake2_256(&post_1);
+        let post_2: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/polkadot_sdk_docs/index.html".into();
+        let post_2_id = sp_io::hashing::blake2_256(&post_2);
+        let post_3: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/frame_support/index.html".into();
+        let post_3_id = sp_io::hashing::blake2_256(&post_3);
+
+        // No pot, no balance
+        assert_eq!(Bullposting::pot_balance(), None);
+
+        PotId::set(Some(frame_support::PalletId(*b"bullpost")));
+        let pot = Bullposting::pot_account().unwrap();
+        // The pot is kept alive with the existential deposit
+        assert_ok!(Balances::mint_into(&pot, 1));
+
+        // The winning voters are paid out of the pot too
+        VoterPayouts::set(true);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_1.clone(), 300));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_2.clone(), 200));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_1.clone(), 500, crate::VoteChoice::Nay));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_2.clone(), 100, crate::VoteChoice::Aye));
+
+        System::set_block_number(1001);
+        let issuance = Balances::total_issuance();
+
+        // The slashed bond goes into the pot instead of being burned
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_1.clone()));
+        System::assert_has_event(
+            Event::SlashedToPot {
+                id: post_1_id,
+                amount: 300,
+            }.into()
+        );
+        assert_eq!(Bullposting::pot_balance(), Some(301));
+        assert_eq!(Balances::total_issuance(), issuance);
+
+        // The reward comes out of the pot instead of being minted
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_2.clone()));
+        System::assert_has_event(
+            Event::VotingResolved {
+                id: post_2_id,
+                submitter: alice,
+                result: crate::Outcome::Bullish,
+                rewarded: 200,
+                slashed: 0,
+                turnout: 100,
+                voter_count: 1,
+            }.into()
+        );
+        assert_eq!(Bullposting::pot_balance(), Some(101));
+        assert_eq!(Balances::total_issuance(), issuance);
+        assert_eq!(Balances::total_balance(&alice), balance - 300 + 200);
+        // The storage rent of both posts is still held until they're ended
+        assert_eq!(Balances::free_balance(alice), balance - 300 + 200 - 2 * 100);
+
+        // The pot can only cover part of bob's payout
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_1));
+        System::assert_has_event(
+            Event::PotShortfall {
+                who: bob,
+                requested: 300,
+                paid: 100,
+            }.into()
+        );
+        assert_eq!(Balances::free_balance(bob), balance + 100);
+        assert_eq!(Bullposting::pot_balance(), Some(1));
+        assert_eq!(Balances::total_issuance(), issuance);
+
+        // Fall back to minting, up to the cap
+        PotShortfall::set(crate::ShortfallBehaviour::MintUpToCap);
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(dave), post_3.clone(), 300));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_3.clone(), 100, crate::VoteChoice::Aye));
+        System::set_block_number(2001);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_3.clone()));
+        System::assert_has_event(
+            Event::PotShortfallMinted {
+                who: dave,
+                amount: 100,
+            }.into()
+        );
+        System::assert_has_event(
+            Event::PotShortfall {
+                who: dave,
+                requested: 300,
+                paid: 100,
+            }.into()
+        );
+        System::assert_has_event(
+            Event::VotingResolved {
+                id: post_3_id,
+                submitter: dave,
+                result: crate::Outcome::Bullish,
+                rewarded: 100,
+                slashed: 0,
+                turnout: 100,
+                voter_count: 1,
+            }.into()
+        );
+        assert_eq!(crate::PeriodShortfallMinted::<Test>::get(), (200, 100));
+        assert_eq!(Balances::total_issuance(), issuance + 100);
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_quorum() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let bond = 300;
+        let balance = 1001;
+        let voting_period = 1000;
+        let post_1: Vec<u8> = "not enough votes".into();
+        let post_1_id = sp_io::hashing::blake2_256(&post_1);
+        let post_2: Vec<u8> = "enough votes".into();
+        let post_2_id = sp_io::hashing::blake2_256(&post_2);
+
+        // Posts need at least 400 Bullish/Bearish votes from at least 2 voters
+        QuorumVotes::set(400);
+        QuorumVoters::set(2);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_1.clone(), bond));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_2.clone(), bond));
+
+        // A split vote must add up to the vote amount
+        assert_noop!(
+            Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_1.clone(), 500, crate::VoteChoice::Split { bull: 300, bear: 100 }),
+            Error::<Test>::InvalidSplit
+        );
+
+        // Enough votes, but only one voter
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_1.clone(), 500, crate::VoteChoice::Nay));
+        // Two voters and enough votes
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_2.clone(), 300, crate::VoteChoice::Nay));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_2.clone(), 100, crate::VoteChoice::Aye));
+
+        System::set_block_number(voting_period + 1);
+
+        // Below quorum, the bond is released with no reward or slash
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_1.clone()));
+        System::assert_last_event(
+            Event::VotingResolved {
+                id: post_1_id,
+                submitter: alice,
+                result: crate::Outcome::NoQuorum,
+                rewarded: 0,
+                slashed: 0,
+                turnout: 500,
+                voter_count: 1,
+            }.into()
+        );
+        assert_eq!(Balances::total_balance(&alice), balance);
+
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_2.clone()));
+        System::assert_has_event(
+            Event::VotingResolved {
+                id: post_2_id,
+                submitter: alice,
+                result: crate::Outcome::Bearish,
+                rewarded: 0,
+                slashed: bond,
+                turnout: 400,
+                voter_count: 2,
+            }.into()
+        );
+        assert_eq!(Balances::total_balance(&alice), balance - bond);
+    });
+}
+
+#[test]
+fn test_try_retract_vote() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let bond = 300;
+        let vote_amount = 500;
+        let voting_period = 1000;
+        let post_url: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/sp_test_primitives/type.BlockNumber.html".into();
+        let post_id = sp_io::hashing::blake2_256(&post_url);
+        let fake_post_url: Vec<u8> = "get rekt kid".into();
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_url.clone(), bond));
+
+        // Can't retract a vote on a non-existant post
+        assert_noop!(Bullposting::try_retract_vote(RuntimeOrigin::signed(bob), fake_post_url), Error::<Test>::PostDoesNotExist);
+
+        // Can't retract a vote that doesn't exist
+        assert_noop!(Bullposting::try_retract_vote(RuntimeOrigin::signed(bob), post_url.clone()), Error::<Test>::VoteDoesNotExist);
+
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_url.clone(), 200, crate::VoteChoice::Nay));
+
+        // Retract the vote
+        assert_ok!(Bullposting::try_retract_vote(RuntimeOrigin::signed(bob), post_url.clone()));
+        System::assert_last_event(
+            Event::VoteRetracted {
+                id: post_id,
+                voter: bob,
+                amount: vote_amount,
+            }.into()
+        );
+
+        // The vote is removed everywhere and unfrozen
+        assert!(!crate::Votes::<Test>::contains_key(bob, post_id));
+        assert_eq!(crate::Posts::<Test>::get(post_id).unwrap().bull_votes, 0);
+        assert_eq!(crate::Posts::<Test>::get(post_id).unwrap().bear_votes, 200);
+        assert_eq!(crate::PostVoters::<Test>::iter_key_prefix(post_id).collect::<Vec<_>>(), vec![charlie]);
+        assert_eq!(crate::VoteCounts::<Test>::get(post_id), Some(1));
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), 0);
+
+        // Voting again is possible
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_url.clone(), vote_amount, crate::VoteChoice::Nay));
+
+        // Retract by ID
+        assert_ok!(Bullposting::try_retract_vote_by_id(RuntimeOrigin::signed(charlie), post_id));
+        assert_eq!(crate::PostVoters::<Test>::iter_key_prefix(post_id).collect::<Vec<_>>(), vec![bob]);
+
+        // Can't retract once the voting period has ended
+        System::set_block_number(voting_period + 1);
+        assert_noop!(Bullposting::try_retract_vote(RuntimeOrigin::signed(bob), post_url), Error::<Test>::VotingEnded);
+    });
+}
+
+#[test]
+fn test_try_withdraw_post() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let bond = 300;
+        let balance = 1001;
+        let post_1: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/sp_test_primitives/type.BlockNumber.html".into();
+        let post_1_id = sp_io::hashing::blake2_256(&post_1);
+        let post_2: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/polkadot_sdk_docs/index.html".into();
+        let post_2_id = sp_io::hashing::blake2_256(&post_2);
+        let fake_post_url: Vec<u8> = "get rekt kid".into();
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_1.clone(), bond));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_2.clone(), bond));
+
+        // Can't withdraw a non-existant post
+        assert_noop!(Bullposting::try_withdraw_post(RuntimeOrigin::signed(alice), fake_post_url), Error::<Test>::PostDoesNotExist);
+
+        // Only the submitter can withdraw their post
+        assert_noop!(Bullposting::try_withdraw_post(RuntimeOrigin::signed(bob), post_1.clone()), Error::<Test>::NotSubmitter);
+
+        // Can't withdraw once someone voted
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_2.clone(), 500, crate::VoteChoice::Nay));
+        assert_noop!(Bullposting::try_withdraw_post_by_id(RuntimeOrigin::signed(alice), post_2_id), Error::<Test>::PostHasVotes);
+
+        // Withdraw, paying the fee out of the bond
+        assert_ok!(Bullposting::try_withdraw_post(RuntimeOrigin::signed(alice), post_1.clone()));
+        System::assert_last_event(
+            Event::PostWithdrawn {
+                id: post_1_id,
+                submitter: alice,
+                fee: 10,
+            }.into()
+        );
+        assert!(!crate::Posts::<Test>::contains_key(post_1_id));
+        assert!(!crate::PostParameters::<Test>::contains_key(post_1_id));
+        assert!(!crate::ExpiringPosts::<Test>::get(1001).contains(&post_1_id));
+        // Only post_2's holds are left
+        assert_eq!(Balances::total_balance_on_hold(&alice), bond + 100);
+        assert_eq!(Balances::total_balance(&alice), balance - 10);
+
+        // The post can be submitted again
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_1.clone(), bond));
+    });
+}
+
+#[test]
+fn test_vote_locks() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let voting_period = 1000;
+        let post_1: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/sp_test_primitives/type.BlockNumber.html".into();
+        let post_1_id = sp_io::hashing::blake2_256(&post_1);
+        let post_2: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/polkadot_sdk_docs/index.html".into();
+        let post_2_id = sp_io::hashing::blake2_256(&post_2);
+        let post_3: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/frame_support/index.html".into();
+        let frozen = |who| Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &who);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_1.clone(), 100));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_2.clone(), 100));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_3.clone(), 100));
+
+        // The freeze is the largest of the account's votes
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_1.clone(), 500, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_2.clone(), 300, crate::VoteChoice::Nay));
+        assert_eq!(frozen(bob), 500);
+        assert_eq!(crate::VoteLocksFor::<Test>::get(bob).into_inner(), vec![(post_1_id, 500), (post_2_id, 300)]);
+
+        // Lowering the largest vote lowers the freeze
+        assert_ok!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_1.clone(), 200, crate::VoteChoice::Aye));
+        assert_eq!(frozen(bob), 300);
+
+        // Retracting a vote leaves the other votes frozen
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_3.clone(), 700, crate::VoteChoice::Aye));
+        assert_eq!(frozen(bob), 700);
+        assert_ok!(Bullposting::try_retract_vote(RuntimeOrigin::signed(bob), post_3.clone()));
+        assert_eq!(frozen(bob), 300);
+
+        // Ending a post only unfreezes what's no longer backing another vote
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_1.clone()));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_1));
+        assert_eq!(frozen(bob), 300);
+        assert_eq!(crate::VoteLocksFor::<Test>::get(bob).into_inner(), vec![(post_2_id, 300)]);
+
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_2.clone()));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_2));
+        assert_eq!(frozen(bob), 0);
+        assert!(!crate::VoteLocksFor::<Test>::contains_key(bob));
+    });
+}
+
+#[test]
+fn test_vote_holds() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let voting_period = 1000;
+        let post_1: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/sp_test_primitives/type.BlockNumber.html".into();
+        let post_2: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/polkadot_sdk_docs/index.html".into();
+        let post_3: Vec<u8> = "https://paritytech.github.io/polkadot-sdk/master/frame_support/index.html".into();
+        let held = |who| Balances::balance_on_hold(&crate::HoldReason::Vote.into(), &who);
+
+        // Each vote is held instead of frozen
+        VoteCollateralMode::set(crate::VoteCollateral::Hold);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_1.clone(), 100));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_2.clone(), 100));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_3.clone(), 100));
+
+        // The same tokens can't back two votes
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_1.clone(), 500, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_2.clone(), 400, crate::VoteChoice::Nay));
+        assert_eq!(held(bob), 900);
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), 0);
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_3.clone(), 200, crate::VoteChoice::Aye), Error::<Test>::InsufficientFreeBalance);
+
+        // Updating only holds or releases the difference
+        assert_ok!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_1.clone(), 300, crate::VoteChoice::Aye));
+        assert_eq!(held(bob), 700);
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_3.clone(), 200, crate::VoteChoice::Aye));
+        assert_eq!(held(bob), 900);
+
+        // Retracting releases the vote's hold
+        assert_ok!(Bullposting::try_retract_vote(RuntimeOrigin::signed(bob), post_2.clone()));
+        assert_eq!(held(bob), 500);
+
+        // Ending a post releases its votes' holds
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_1.clone()));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_1));
+        assert_eq!(held(bob), 200);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post_3.clone()));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post_3));
+        assert_eq!(held(bob), 0);
+    });
+}
+
+#[test]
+fn test_runtime_api_queries() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let bond = 300;
+        let voting_period = 1000;
+        let post: Vec<u8> = "queried post".into();
+        let post_id = sp_io::hashing::blake2_256(&post);
+
+        // Voter payouts are off by default, turn them on to preview the voters' share
+        VoterPayouts::set(true);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_eq!(Bullposting::post_details(post_id), None);
+        assert_eq!(Bullposting::preview_resolution(post_id), None);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), 200, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post.clone(), 100, crate::VoteChoice::Nay));
+
+        assert_eq!(
+            Bullposting::post_details(post_id),
+            Some(crate::PostDetails {
+                id: post_id,
+                submitter: alice,
+                bond,
+                bull_votes: 200,
+                bear_votes: 100,
+                abstain_votes: 0,
+                bull_weight: 200,
+                bear_weight: 100,
+                voting_until: voting_period + 1,
+                resolved: false,
+                voter_count: 2,
+            })
+        );
+        assert_eq!(Bullposting::account_votes(&bob), vec![(post_id, 200, crate::VoteChoice::Aye)]);
+        let mut voters = Bullposting::post_voters(post_id);
+        voters.sort();
+        assert_eq!(voters, vec![bob, charlie]);
+
+        // Bullish with the current votes, rewarded 100% of the bond
+        assert_eq!(
+            Bullposting::preview_resolution(post_id),
+            Some(crate::ResolutionPreview {
+                result: crate::Outcome::Bullish,
+                reward: bond,
+                slash: 0,
+                voter_share: 0,
+                turnout: 300,
+                voter_count: 2,
+            })
+        );
+
+        // Bearish after Bob changes their vote, the slash is shared among the voters
+        assert_ok!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post.clone(), 200, crate::VoteChoice::Nay));
+        assert_eq!(
+            Bullposting::preview_resolution(post_id),
+            Some(crate::ResolutionPreview {
+                result: crate::Outcome::Bearish,
+                reward: 0,
+                slash: bond,
+                voter_share: bond,
+                turnout: 300,
+                voter_count: 2,
+            })
+        );
+
+        // The preview matches the actual resolution, after which there's nothing left to preview
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post.clone()));
+        System::assert_has_event(
+            Event::VotingResolved {
+                id: post_id,
+                submitter: alice,
+                result: crate::Outcome::Bearish,
+                rewarded: 0,
+                slashed: bond,
+                turnout: 300,
+                voter_count: 2,
+            }.into()
+        );
+        assert_eq!(Bullposting::preview_resolution(post_id), None);
+    });
+}
+
+#[test]
+fn test_offchain_post_text() {
+    let alice = 0;
+    let bond = 300;
+    let post: Vec<u8> = "indexed post".into();
+    let post_id = sp_io::hashing::blake2_256(&post);
+
+    let mut ext = new_test_ext();
+    ext.execute_with(|| {
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+    });
+
+    // The full input is written to the offchain index under the post ID
+    ext.persist_offchain_overlay();
+    assert_eq!(ext.offchain_db().get(&crate::offchain_post_key(&post_id)), Some(post.clone()));
+
+    // Withdrawing the post clears its text from the index
+    ext.execute_with(|| {
+        assert_ok!(Bullposting::try_withdraw_post(RuntimeOrigin::signed(alice), post));
+    });
+    ext.persist_offchain_overlay();
+    assert_eq!(ext.offchain_db().get(&crate::offchain_post_key(&post_id)), None);
+}
+
+#[test]
+fn test_commit_reveal() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let dave = 3;
+        let bond = 300;
+        let balance = 1001;
+        let voting_period = 1000;
+        let reveal_period = 100;
+        let post: Vec<u8> = "hidden votes".into();
+        let post_id = sp_io::hashing::blake2_256(&post);
+        let bob_salt = [1u8; 32];
+        let charlie_salt = [2u8; 32];
+        let bob_commitment = Bullposting::vote_commitment(&bob, &post_id, &crate::VoteChoice::Nay, 300, &bob_salt);
+        let charlie_commitment = Bullposting::vote_commitment(&charlie, &post_id, &crate::VoteChoice::Aye, 100, &charlie_salt);
+        let dave_commitment = Bullposting::vote_commitment(&dave, &post_id, &crate::VoteChoice::Aye, 200, &[3u8; 32]);
+
+        CommitReveal::set(true);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+
+        // Votes on commit-reveal posts must be committed
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), 300, crate::VoteChoice::Nay), Error::<Test>::CommitRevealRequired);
+
+        assert_ok!(Bullposting::try_commit_vote(RuntimeOrigin::signed(bob), post.clone(), bob_commitment, 400));
+        System::assert_last_event(Event::VoteCommitted { id: post_id, voter: bob, collateral: 400 }.into());
+        assert_ok!(Bullposting::try_commit_vote_by_id(RuntimeOrigin::signed(charlie), post_id, charlie_commitment, 100));
+        assert_ok!(Bullposting::try_commit_vote(RuntimeOrigin::signed(dave), post.clone(), dave_commitment, 200));
+        assert_noop!(Bullposting::try_commit_vote(RuntimeOrigin::signed(bob), post.clone(), bob_commitment, 400), Error::<Test>::AlreadyVoted);
+
+        // Another account can copy Bob's commitment, but can't reveal it
+        assert_ok!(Bullposting::try_commit_vote(RuntimeOrigin::signed(10000), post.clone(), bob_commitment, 300));
+
+        // The collateral is frozen, but the tallies stay hidden
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), 400);
+        let post_struct = crate::Posts::<Test>::get(post_id).unwrap();
+        assert_eq!((post_struct.bull_votes, post_struct.bear_votes), (0, 0));
+
+        // Commitments are revealed after the voting period ends
+        assert_noop!(Bullposting::try_reveal_vote(RuntimeOrigin::signed(bob), post.clone(), crate::VoteChoice::Nay, 300, bob_salt), Error::<Test>::RevealNotOpen);
+        System::set_block_number(voting_period + 1);
+        assert_noop!(Bullposting::try_commit_vote(RuntimeOrigin::signed(10000), post.clone(), bob_commitment, 400), Error::<Test>::VotingEnded);
+
+        // The reveal must match the commitment
+        assert_noop!(Bullposting::try_reveal_vote(RuntimeOrigin::signed(bob), post.clone(), crate::VoteChoice::Aye, 300, bob_salt), Error::<Test>::InvalidReveal);
+        assert_noop!(Bullposting::try_reveal_vote(RuntimeOrigin::signed(bob), post.clone(), crate::VoteChoice::Nay, 300, charlie_salt), Error::<Test>::InvalidReveal);
+        assert_noop!(Bullposting::try_reveal_vote(RuntimeOrigin::signed(10000), post.clone(), crate::VoteChoice::Nay, 300, bob_salt), Error::<Test>::InvalidReveal);
+
+        // Revealing only keeps the revealed amount frozen
+        assert_ok!(Bullposting::try_reveal_vote(RuntimeOrigin::signed(bob), post.clone(), crate::VoteChoice::Nay, 300, bob_salt));
+        System::assert_last_event(Event::VoteRevealed { id: post_id, voter: bob, vote_amount: 300, vote: crate::VoteChoice::Nay }.into());
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), 300);
+        assert_ok!(Bullposting::try_reveal_vote_by_id(RuntimeOrigin::signed(charlie), post_id, crate::VoteChoice::Aye, 100, charlie_salt));
+
+        let post_struct = crate::Posts::<Test>::get(post_id).unwrap();
+        assert_eq!((post_struct.bull_votes, post_struct.bear_votes), (100, 300));
+        assert_eq!(crate::VoteCounts::<Test>::get(post_id), Some(2));
+
+        // The post is resolved once the reveal period ends
+        assert_noop!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post.clone()), Error::<Test>::VotingStillOngoing);
+        System::set_block_number(voting_period + reveal_period + 1);
+        assert_noop!(Bullposting::try_reveal_vote(RuntimeOrigin::signed(dave), post.clone(), crate::VoteChoice::Aye, 200, [3u8; 32]), Error::<Test>::RevealNotOpen);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(bob), post.clone()));
+        System::assert_has_event(
+            Event::VotingResolved {
+                id: post_id,
+                submitter: alice,
+                result: crate::Outcome::Bearish,
+                rewarded: 0,
+                slashed: bond,
+                turnout: 400,
+                voter_count: 2,
+            }.into()
+        );
+
+        // Dave never revealed, and forfeits half of their collateral when the post is ended
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(bob), post.clone()));
+        System::assert_has_event(Event::CommitmentForfeited { id: post_id, voter: dave, amount: 100 }.into());
+        System::assert_has_event(Event::CommitmentForfeited { id: post_id, voter: 10000, amount: 150 }.into());
+        assert_eq!(Balances::total_balance(&dave), balance - 100);
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &dave), 0);
+        assert!(crate::Commitments::<Test>::get(dave, post_id).is_none());
+        assert!(!crate::Posts::<Test>::contains_key(post_id));
+    });
+}
+
+#[test]
+fn test_forfeit_frozen_collateral() {
+    build_and_execute(|| {
+        let alice = 0;
+        let dave = 3;
+        let balance = 1001;
+        let voting_period = 1000;
+        let reveal_period = 100;
+        let post_1: Vec<u8> = "never revealed".into();
+        let post_1_id = sp_io::hashing::blake2_256(&post_1);
+        let post_2: Vec<u8> = "revealed".into();
+        let post_2_id = sp_io::hashing::blake2_256(&post_2);
+        let salt = [4u8; 32];
+
+        CommitReveal::set(true);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_1.clone(), 100));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_2.clone(), 100));
+
+        // Dave's tokens all back the vote on the second post, which outlives the first one
+        let commitment = Bullposting::vote_commitment(&dave, &post_2_id, &crate::VoteChoice::Aye, 1000, &salt);
+        assert_ok!(Bullposting::try_commit_vote(RuntimeOrigin::signed(dave), post_1.clone(), [0u8; 32], 200));
+        assert_ok!(Bullposting::try_commit_vote(RuntimeOrigin::signed(dave), post_2.clone(), commitment, 1000));
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_reveal_vote(RuntimeOrigin::signed(dave), post_2, crate::VoteChoice::Aye, 1000, salt));
+
+        // The forfeit is taken out of the frozen collateral, even though the tokens stay frozen by the other vote
+        System::set_block_number(voting_period + reveal_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(alice), post_1.clone()));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(alice), post_1));
+        System::assert_has_event(Event::CommitmentForfeited { id: post_1_id, voter: dave, amount: 100 }.into());
+        assert_eq!(Balances::total_balance(&dave), balance - 100);
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &dave), 1000);
+        assert!(crate::Commitments::<Test>::get(dave, post_1_id).is_none());
+    });
+}
+
+#[test]
+fn test_forfeit_held_collateral() {
+    build_and_execute(|| {
+        let alice = 0;
+        let dave = 3;
+        let balance = 1001;
+        let voting_period = 1000;
+        let reveal_period = 100;
+        let post: Vec<u8> = "never revealed".into();
+        let post_id = sp_io::hashing::blake2_256(&post);
+        let held = |who| Balances::balance_on_hold(&crate::HoldReason::Vote.into(), &who);
+
+        CommitReveal::set(true);
+        VoteCollateralMode::set(crate::VoteCollateral::Hold);
+        PotId::set(Some(frame_support::PalletId(*b"bullpost")));
+        let pot = Bullposting::pot_account().unwrap();
+        // The pot is kept alive with the existential deposit
+        assert_ok!(Balances::mint_into(&pot, 1));
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), 100));
+        assert_ok!(Bullposting::try_commit_vote(RuntimeOrigin::signed(dave), post.clone(), [0u8; 32], 1000));
+        assert_eq!(held(dave), 1000);
+
+        // The forfeit moves from the hold into the pot, and the rest of the collateral is released
+        System::set_block_number(voting_period + reveal_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(alice), post.clone()));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(alice), post));
+        System::assert_has_event(Event::CommitmentForfeited { id: post_id, voter: dave, amount: 500 }.into());
+        assert_eq!(held(dave), 0);
+        assert_eq!(Balances::total_balance(&dave), balance - 500);
+        assert_eq!(Balances::free_balance(pot), 1 + 500);
+    });
+}
+
+#[test]
+fn test_delegation() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let dave = 3;
+        let bond = 300;
+        let voting_period = 1000;
+        let post_1: Vec<u8> = "first delegated post".into();
+        let post_1_id = sp_io::hashing::blake2_256(&post_1);
+        let post_2: Vec<u8> = "second delegated post".into();
+        let post_2_id = sp_io::hashing::blake2_256(&post_2);
+        let tally = |id| {
+            let post = crate::Posts::<Test>::get(id).unwrap();
+            (post.bull_votes, post.bear_votes)
+        };
+        let frozen = |who| Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &who);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_noop!(Bullposting::delegate(RuntimeOrigin::signed(charlie), charlie, 200), Error::<Test>::CannotDelegateToSelf);
+        assert_noop!(Bullposting::undelegate(RuntimeOrigin::signed(charlie)), Error::<Test>::NotDelegating);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_1.clone(), bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_1.clone(), 100, crate::VoteChoice::Aye));
+
+        // Delegating adds to the delegate's open votes and locks the delegated balance
+        assert_ok!(Bullposting::delegate(RuntimeOrigin::signed(charlie), bob, 200));
+        System::assert_last_event(Event::Delegated { delegator: charlie, delegate: bob, amount: 200 }.into());
+        assert_noop!(Bullposting::delegate(RuntimeOrigin::signed(charlie), bob, 200), Error::<Test>::AlreadyDelegating);
+        assert_eq!(frozen(charlie), 200);
+        assert_eq!(tally(post_1_id), (300, 0));
+
+        // The frozen tokens can't back the delegation and a vote at the same time
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post_1.clone(), 200, crate::VoteChoice::Nay), Error::<Test>::AlreadyDelegating);
+        assert_noop!(Bullposting::delegate(RuntimeOrigin::signed(bob), dave, 100), Error::<Test>::AlreadyVoting);
+
+        assert_ok!(Bullposting::delegate(RuntimeOrigin::signed(dave), bob, 150));
+        assert_eq!(tally(post_1_id), (450, 0));
+
+        // New votes count with everything delegated so far
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post_2.clone(), bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post_2.clone(), 100, crate::VoteChoice::Nay));
+        assert_eq!(tally(post_2_id), (0, 450));
+
+        // Undelegating removes the balance from open votes and unlocks it
+        assert_ok!(Bullposting::undelegate(RuntimeOrigin::signed(charlie)));
+        System::assert_last_event(Event::Undelegated { delegator: charlie, delegate: bob, amount: 200 }.into());
+        assert_eq!(frozen(charlie), 0);
+        assert_eq!(tally(post_1_id), (250, 0));
+        assert_eq!(tally(post_2_id), (0, 250));
+
+        // Updated votes move the delegated balance along with them
+        assert_ok!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post_1.clone(), 50, crate::VoteChoice::Nay));
+        assert_eq!(tally(post_1_id), (0, 200));
+
+        // Once voting ends, the tallies no longer change
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::undelegate(RuntimeOrigin::signed(dave)));
+        assert_eq!(frozen(dave), 0);
+        assert_eq!(tally(post_1_id), (0, 200));
+        assert_eq!(tally(post_2_id), (0, 250));
+    });
+}
+
+#[test]
+fn test_conviction() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let bond = 300;
+        let voting_period = 1000;
+        let post: Vec<u8> = "a post voted on with conviction".into();
+        let post_id = sp_io::hashing::blake2_256(&post);
+        let tally = |id| {
+            let post = crate::Posts::<Test>::get(id).unwrap();
+            (post.bull_weight, post.bear_weight)
+        };
+        let frozen = |who| Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &who);
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        assert_ok!(Bullposting::set_conviction(RuntimeOrigin::signed(bob), crate::Conviction::Locked2x));
+        System::assert_last_event(Event::ConvictionSet { who: bob, conviction: crate::Conviction::Locked2x }.into());
+
+        // The vote's weight is multiplied by the conviction
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), 100, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post.clone(), 150, crate::VoteChoice::Nay));
+        assert_eq!(tally(post_id), (200, 150));
+        assert_eq!(crate::Posts::<Test>::get(post_id).unwrap().bull_votes, 100);
+
+        // Updated votes are cast with the account's current conviction
+        assert_ok!(Bullposting::set_conviction(RuntimeOrigin::signed(bob), crate::Conviction::Locked3x));
+        assert_ok!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post.clone(), 100, crate::VoteChoice::Aye));
+        assert_eq!(tally(post_id), (300, 150));
+
+        // Once the post ends, votes without a conviction are unfrozen and the others stay locked
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(alice), post.clone()));
+        System::set_block_number(voting_period + 500);
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(alice), post.clone()));
+        assert_eq!(frozen(charlie), 0);
+        assert_eq!(frozen(bob), 100);
+        assert_noop!(Bullposting::unlock(RuntimeOrigin::signed(bob)), Error::<Test>::NothingToUnlock);
+        assert_noop!(Bullposting::unlock(RuntimeOrigin::signed(charlie)), Error::<Test>::NothingToUnlock);
+
+        // Locked3x keeps the vote locked for 2 voting periods after the post's voting ended, not after it was ended
+        assert_eq!(crate::LockExpiries::<Test>::get(bob).to_vec(), vec![(3 * voting_period + 1, 100)]);
+        System::set_block_number(2 * voting_period + 1);
+        assert_noop!(Bullposting::unlock(RuntimeOrigin::signed(bob)), Error::<Test>::NothingToUnlock);
+
+        System::set_block_number(3 * voting_period + 1);
+        assert_ok!(Bullposting::unlock(RuntimeOrigin::signed(bob)));
+        System::assert_last_event(Event::Unlocked { who: bob, amount: 100 }.into());
+        assert_eq!(frozen(bob), 0);
+        assert!(!crate::LockExpiries::<Test>::contains_key(bob));
+    });
+}
+
+#[test]
+fn test_tally_modes() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let dave = 3;
+        let bond = 300;
+        let voting_period = 1000;
+        let quadratic_post: Vec<u8> = "a post tallied quadratically".into();
+        let quadratic_id = sp_io::hashing::blake2_256(&quadratic_post);
+        let one_vote_post: Vec<u8> = "a post tallied one account one vote".into();
+        let one_vote_id = sp_io::hashing::blake2_256(&one_vote_post);
+        let post = |id| crate::Posts::<Test>::get(id).unwrap();
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        // A whale outvotes two smaller voters by raw amount, but not by the square root of it
+        VoteTallyMode::set(crate::TallyMode::Quadratic);
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), quadratic_post.clone(), bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), quadratic_post.clone(), 900, crate::VoteChoice::Nay));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), quadratic_post.clone(), 400, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(dave), quadratic_post.clone(), 400, crate::VoteChoice::Aye));
+        let quadratic = post(quadratic_id);
+        assert_eq!((quadratic.bull_votes, quadratic.bear_votes), (800, 900));
+        assert_eq!((quadratic.bull_weight, quadratic.bear_weight), (40, 30));
+
+        // Each delegation counts by its own square root, so pooling balances in a delegate doesn't outweigh voting with them
+        for delegator in [4, 5] {
+            Balances::set_balance(&delegator, 1001);
+            assert_ok!(Bullposting::delegate(RuntimeOrigin::signed(delegator), charlie, 100));
+        }
+        let quadratic = post(quadratic_id);
+        assert_eq!((quadratic.bull_votes, quadratic.bull_weight), (1000, 60));
+        assert_ok!(Bullposting::undelegate(RuntimeOrigin::signed(4)));
+        assert_eq!(post(quadratic_id).bull_weight, 50);
+        assert_ok!(Bullposting::undelegate(RuntimeOrigin::signed(5)));
+
+        // Every vote counts once, as long as it's at least `VoteMinimum`
+        VoteTallyMode::set(crate::TallyMode::OneAccountOneVote);
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), one_vote_post.clone(), bond));
+        assert_noop!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), one_vote_post.clone(), 49, crate::VoteChoice::Nay), Error::<Test>::VoteTooLow);
+        // A single account's vote can't be split
+        assert_noop!(
+            Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), one_vote_post.clone(), 900, crate::VoteChoice::Split { bull: 450, bear: 450 }),
+            Error::<Test>::SplitNotAllowed
+        );
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), one_vote_post.clone(), 900, crate::VoteChoice::Nay));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), one_vote_post.clone(), 50, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(dave), one_vote_post.clone(), 50, crate::VoteChoice::Aye));
+        let one_vote = post(one_vote_id);
+        assert_eq!((one_vote.bull_votes, one_vote.bear_votes), (100, 900));
+        assert_eq!((one_vote.bull_weight, one_vote.bear_weight), (2, 1));
+
+        // Each post is resolved with the tally mode it was submitted under
+        VoteTallyMode::set(crate::TallyMode::Linear);
+        System::set_block_number(voting_period + 1);
+        assert_eq!(Bullposting::preview_resolution(quadratic_id).unwrap().result, crate::Outcome::Bullish);
+        assert_eq!(Bullposting::preview_resolution(one_vote_id).unwrap().result, crate::Outcome::Bullish);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(alice), quadratic_post));
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(alice), one_vote_post));
+        assert!(post(quadratic_id).resolved);
+        assert!(post(one_vote_id).resolved);
+    });
+}
+
+#[test]
+fn test_vote_choices() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let dave = 3;
+        let bond = 300;
+        let voting_period = 1000;
+        let post: Vec<u8> = "a post with split and abstaining votes".into();
+        let post_id = sp_io::hashing::blake2_256(&post);
+        let totals = |id| {
+            let post = crate::Posts::<Test>::get(id).unwrap();
+            (post.bull_votes, post.bear_votes, post.abstain_votes, post.bull_weight, post.bear_weight)
+        };
+
+        // Go past genesis block so events get deposited
+        System::set_block_number(1);
+
+        QuorumVotes::set(700);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+
+        // Split votes count on both sides, abstaining votes only towards the quorum
+        let split = crate::VoteChoice::Split { bull: 300, bear: 100 };
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), 400, split.clone()));
+        System::assert_last_event(Event::VoteSubmitted { id: post_id, voter: bob, vote_amount: 400, vote: split }.into());
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post.clone(), 200, crate::VoteChoice::Abstain));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(dave), post.clone(), 100, crate::VoteChoice::Aye));
+        assert_eq!(totals(post_id), (400, 100, 200, 400, 100));
+
+        // Updating moves the whole vote to its new choice
+        assert_ok!(Bullposting::try_update_vote(RuntimeOrigin::signed(bob), post.clone(), 400, crate::VoteChoice::Nay));
+        System::assert_last_event(Event::VoteUpdated { id: post_id, voter: bob, vote_amount: 400, vote: crate::VoteChoice::Nay }.into());
+        assert_eq!(totals(post_id), (100, 400, 200, 100, 400));
+
+        // The abstaining votes make up the quorum
+        let preview = Bullposting::preview_resolution(post_id).unwrap();
+        assert_eq!((preview.result, preview.turnout), (crate::Outcome::Bearish, 700));
+
+        assert_ok!(Bullposting::try_retract_vote(RuntimeOrigin::signed(charlie), post.clone()));
+        assert_eq!(totals(post_id), (100, 400, 0, 100, 400));
+
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(alice), post.clone()));
+        System::assert_last_event(
+            Event::VotingResolved {
+                id: post_id,
+                submitter: alice,
+                result: crate::Outcome::NoQuorum,
+                rewarded: 0,
+                slashed: 0,
+                turnout: 500,
+                voter_count: 2,
+            }.into()
+        );
+    });
+}
+
+#[test]
+fn test_try_state() {
+    build_and_execute(|| {
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let bond = 300;
+        let post: Vec<u8> = "Invariants hold".into();
+        let post_id = sp_io::hashing::blake2_256(&post);
+        let missing_id = sp_io::hashing::blake2_256(b"missing");
+        let broken = |error: &'static str| assert_eq!(Bullposting::do_try_state(), Err(error.into()));
+
+        // Holds on a fresh chain
+        assert_ok!(Bullposting::do_try_state());
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), 100, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post.clone(), 200, crate::VoteChoice::Nay));
+        assert_ok!(Bullposting::do_try_state());
+
+        // Tallies that don't match the votes
+        crate::Posts::<Test>::mutate(post_id, |post| post.as_mut().unwrap().bull_votes += 1);
+        broken("Post tallies don't match its votes");
+        crate::Posts::<Test>::mutate(post_id, |post| post.as_mut().unwrap().bull_votes -= 1);
+
+        // A vote count that doesn't match the votes
+        crate::VoteCounts::<Test>::insert(post_id, 3);
+        broken("VoteCounts doesn't match the votes on the post");
+        crate::VoteCounts::<Test>::insert(post_id, 2);
+
+        // A vote on a post that doesn't exist
+        crate::Votes::<Test>::insert(bob, missing_id, (100, crate::VoteChoice::Aye));
+        broken("Vote on a post that doesn't exist");
+        crate::Votes::<Test>::remove(bob, missing_id);
+
+        // A bond that isn't held
+        assert_ok!(Balances::release(&crate::HoldReason::PostBond.into(), &alice, 1, frame_support::traits::tokens::Precision::Exact));
+        broken("Bond hold doesn't cover the submitter's bonds");
+        assert_ok!(Balances::hold(&crate::HoldReason::PostBond.into(), &alice, 1));
+
+        // A vote that isn't frozen
+        assert_ok!(Balances::set_freeze(&crate::FreezeReason::Vote.into(), &charlie, 100));
+        broken("Vote freeze doesn't cover the account's locks");
+        assert_ok!(Balances::set_freeze(&crate::FreezeReason::Vote.into(), &charlie, 200));
+
+        assert_ok!(Bullposting::do_try_state());
+    });
+}
+
+#[test]
+fn test_post_voters_migration() {
+    build_and_execute(|| {
+        use frame_support::migrations::SteppedMigration;
+        use frame_support::traits::{GetStorageVersion, StorageVersion};
+        use frame_support::weights::WeightMeter;
+        use crate::migrations::v1::{v0, MigrateToPostVoters};
+
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let dave = 3;
+        let bond = 300;
+        let voting_period = 1000;
+        let post: Vec<u8> = "Voters lists are migrated".into();
+        let post_id = sp_io::hashing::blake2_256(&post);
+        let hidden_post: Vec<u8> = "Commitments are counted".into();
+        let hidden_id = sp_io::hashing::blake2_256(&hidden_post);
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), 100, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post.clone(), 200, crate::VoteChoice::Nay));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(dave), post.clone(), 300, crate::VoteChoice::Aye));
+
+        CommitReveal::set(true);
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), hidden_post.clone(), bond));
+        let commitment = Bullposting::vote_commitment(&bob, &hidden_id, &crate::VoteChoice::Aye, 100, &[1u8; 32]);
+        assert_ok!(Bullposting::try_commit_vote(RuntimeOrigin::signed(bob), hidden_post, commitment, 100));
+
+        // Store the voters the way they were before the migration, before commitments were counted
+        for id in [post_id, hidden_id] {
+            let voters: Vec<u64> = crate::PostVoters::<Test>::iter_key_prefix(id).collect();
+            let _ = crate::PostVoters::<Test>::clear_prefix(id, u32::MAX, None);
+            v0::Voters::<Test>::insert(id, voters);
+        }
+        crate::CommitmentCounts::<Test>::remove(hidden_id);
+        StorageVersion::new(0).put::<Bullposting>();
+
+        // A step needs the weight to read a list and move at least one voter
+        let required = MigrateToPostVoters::<Test>::list_weight().saturating_add(MigrateToPostVoters::<Test>::voter_weight());
+        assert!(MigrateToPostVoters::<Test>::step(None, &mut WeightMeter::with_limit(Weight::zero())).is_err());
+
+        // A list too long for one step is moved over several, writing back what's left of it
+        let mut meter = WeightMeter::with_limit(required);
+        let mut cursor = MigrateToPostVoters::<Test>::step(None, &mut meter).unwrap();
+        assert!(cursor.is_some());
+        assert_eq!(crate::PostVoters::<Test>::iter().count(), 1);
+        assert_eq!(v0::Voters::<Test>::iter_values().map(|voters| voters.len()).sum::<usize>(), 3);
+
+        while cursor.is_some() {
+            cursor = MigrateToPostVoters::<Test>::step(cursor, &mut WeightMeter::with_limit(required)).unwrap();
+        }
+        assert_eq!(Bullposting::on_chain_storage_version(), 1);
+        assert!(v0::Voters::<Test>::iter_keys().next().is_none());
+        let mut voters = Bullposting::post_voters(post_id);
+        voters.sort();
+        assert_eq!(voters, vec![bob, charlie, dave]);
+
+        // The unrevealed commitment counts towards `MaxVoters` again
+        assert_eq!(Bullposting::post_voters(hidden_id), vec![bob]);
+        assert_eq!(crate::CommitmentCounts::<Test>::get(hidden_id), 1);
+
+        // Running it again changes nothing
+        assert!(matches!(MigrateToPostVoters::<Test>::step(None, &mut WeightMeter::new()), Ok(None)));
+        assert_eq!(Bullposting::post_voters(post_id).len(), 3);
+        StorageVersion::new(2).put::<Bullposting>();
+
+        // The migrated voters are unfrozen when the post is ended
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(alice), post.clone()));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(alice), post));
+        assert!(!crate::Posts::<Test>::contains_key(post_id));
+        assert!(crate::PostVoters::<Test>::iter_key_prefix(post_id).next().is_none());
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &charlie), 0);
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &dave), 0);
+    });
+}
+
+#[test]
+fn test_multi_block_migration() {
+    build_and_execute(|| {
+        use frame_support::migrations::{SteppedMigration, SteppedMigrationError};
+        use frame_support::traits::{GetStorageVersion, StorageVersion};
+        use frame_support::weights::WeightMeter;
+        use crate::migrations::v2::{v1, Cursor, MigrateToV2};
+
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let bond = 300;
+        let post: Vec<u8> = "Posts are migrated to the new layout".into();
+        let post_id = sp_io::hashing::blake2_256(&post);
+        let other_post: Vec<u8> = "Without any votes".into();
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), other_post, bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), 100, crate::VoteChoice::Aye));
+        // Stored the same as a `Tie` vote before the migration
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(charlie), post.clone(), 200, crate::VoteChoice::Abstain));
+
+        // Store the posts the way they were before the migration, with tie votes left out of the tallies
+        for (id, post) in crate::Posts::<Test>::iter().collect::<Vec<_>>() {
+            v1::Posts::<Test>::insert(id, v1::Post {
+                submitter: post.submitter,
+                bond: post.bond,
+                bull_votes: post.bull_votes,
+                bear_votes: post.bear_votes,
+                voting_until: post.voting_until,
+                resolved: post.resolved,
+            });
+        }
+
+        // It doesn't run on other versions
+        StorageVersion::new(0).put::<Bullposting>();
+        assert!(matches!(MigrateToV2::<Test>::step(None, &mut WeightMeter::new()), Ok(None)));
+        assert_eq!(Bullposting::on_chain_storage_version(), 0);
+        StorageVersion::new(1).put::<Bullposting>();
+
+        // While the runtime's migrations run, the pallet is paused
+        MigrationsOngoing::set(true);
+        assert_noop!(Bullposting::try_submit_post(RuntimeOrigin::signed(bob), "Paused".into(), bond), Error::<Test>::MigrationOngoing);
+        assert_noop!(Bullposting::try_submit_vote_by_id(RuntimeOrigin::signed(bob), post_id, 100, crate::VoteChoice::Aye), Error::<Test>::MigrationOngoing);
+
+        // A step that can't migrate anything fails, so it's retried with more weight
+        let step_weight = MigrateToV2::<Test>::step_weight();
+        assert!(matches!(
+            MigrateToV2::<Test>::step(None, &mut WeightMeter::with_limit(Weight::zero())),
+            Err(SteppedMigrationError::InsufficientWeight { required }) if required == step_weight
+        ));
+
+        // Each step migrates as many posts, then votes, as its weight allows
+        let mut meter = WeightMeter::with_limit(step_weight.saturating_mul(2));
+        let mut cursor = MigrateToV2::<Test>::step(None, &mut meter).unwrap();
+        assert!(matches!(cursor, Some(Cursor::Posts(Some(_)))));
+        assert_eq!(meter.consumed(), step_weight.saturating_mul(2));
+        assert_eq!(Bullposting::on_chain_storage_version(), 1);
+
+        let mut steps = 1;
+        while cursor.is_some() {
+            cursor = MigrateToV2::<Test>::step(cursor, &mut WeightMeter::with_limit(step_weight.saturating_mul(2))).unwrap();
+            steps += 1;
+        }
+        // Two posts, then two votes, with a step to move from one to the other and a step to find nothing left
+        assert_eq!(steps, 3);
+        assert_eq!(Bullposting::on_chain_storage_version(), 2);
+        MigrationsOngoing::set(false);
+
+        // The tie vote is counted as abstaining
+        let migrated = crate::Posts::<Test>::get(post_id).unwrap();
+        assert_eq!((migrated.bull_votes, migrated.bear_votes, migrated.abstain_votes), (100, 0, 200));
+        assert_eq!((migrated.bull_weight, migrated.bear_weight), (100, 0));
+
+        // Running it again changes nothing, and the pallet is unpaused
+        assert!(matches!(MigrateToV2::<Test>::step(None, &mut WeightMeter::new()), Ok(None)));
+        assert_eq!(crate::Posts::<Test>::get(post_id).unwrap().abstain_votes, 200);
+        assert_ok!(Bullposting::try_submit_vote_by_id(RuntimeOrigin::signed(3), post_id, 100, crate::VoteChoice::Nay));
+    });
+}
+
+#[test]
+fn test_vote_locks_migration() {
+    build_and_execute(|| {
+        use frame_support::migrations::SteppedMigration;
+        use frame_support::traits::{GetStorageVersion, StorageVersion};
+        use frame_support::weights::WeightMeter;
+        use crate::migrations::v3::MigrateToV3;
+
+        let alice = 0;
+        let bob = 1;
+        let bond = 300;
+        let voting_period = 1000;
+        let post: Vec<u8> = "Vote locks are rebuilt".into();
+        let other_post: Vec<u8> = "From the votes".into();
+
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), other_post.clone(), bond));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), 300, crate::VoteChoice::Aye));
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), other_post.clone(), 100, crate::VoteChoice::Nay));
+
+        // Votes cast before the locks were tracked only have the freeze
+        crate::VoteLocksFor::<Test>::remove(bob);
+        StorageVersion::new(2).put::<Bullposting>();
+
+        let step_weight = MigrateToV3::<Test>::step_weight();
+        let mut cursor = MigrateToV3::<Test>::step(None, &mut WeightMeter::with_limit(step_weight)).unwrap();
+        assert_eq!(crate::VoteLocksFor::<Test>::get(bob).len(), 1);
+        while cursor.is_some() {
+            cursor = MigrateToV3::<Test>::step(cursor, &mut WeightMeter::with_limit(step_weight)).unwrap();
+        }
+        assert_eq!(Bullposting::on_chain_storage_version(), 3);
+
+        let mut locks = crate::VoteLocksFor::<Test>::get(bob).into_inner();
+        locks.sort_by_key(|(_, amount)| *amount);
+        assert_eq!(locks, vec![(sp_io::hashing::blake2_256(&other_post), 100), (sp_io::hashing::blake2_256(&post), 300)]);
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), 300);
+
+        // Running it again changes nothing
+        assert!(matches!(MigrateToV3::<Test>::step(None, &mut WeightMeter::new()), Ok(None)));
+        assert_eq!(crate::VoteLocksFor::<Test>::get(bob).len(), 2);
+
+        // Ending one post leaves the other vote frozen
+        System::set_block_number(voting_period + 1);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(alice), post.clone()));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(alice), post));
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), 100);
+    });
+}
+
+#[test]
+fn test_legacy_vote_locks_migration() {
+    build_and_execute(|| {
+        use frame_support::migrations::SteppedMigration;
+        use frame_support::traits::StorageVersion;
+        use frame_support::weights::WeightMeter;
+        use crate::migrations::v3::MigrateToV3;
+
+        let alice = 0;
+        let bob = 1;
+        let bond = 50;
+        let voting_period = 1000;
+        let max_votes = <Test as crate::Config>::MaxVotesPerAccount::get();
+        let posts: Vec<Vec<u8>> = (0..=max_votes).map(|i| format!("Legacy vote {i}").into_bytes()).collect();
+        let largest = posts[max_votes as usize].clone();
+
+        // Only `MaxExpiringPerBlock` posts can be submitted in one block
+        Balances::set_balance(&alice, 1_000_000);
+        for post in &posts[..max_votes as usize] {
+            assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), post.clone(), bond));
+        }
+        System::set_block_number(2);
+        assert_ok!(Bullposting::try_submit_post(RuntimeOrigin::signed(alice), largest.clone(), bond));
+        for post in &posts[..max_votes as usize] {
+            assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), post.clone(), 50, crate::VoteChoice::Aye));
+        }
+
+        // Back then an account could vote on more posts than `MaxVotesPerAccount`, so its last vote has no lock
+        let locks = crate::VoteLocksFor::<Test>::take(bob);
+        assert_ok!(Bullposting::try_submit_vote(RuntimeOrigin::signed(bob), largest.clone(), 300, crate::VoteChoice::Aye));
+        crate::VoteLocksFor::<Test>::insert(bob, locks);
+        StorageVersion::new(2).put::<Bullposting>();
+
+        let step_weight = MigrateToV3::<Test>::step_weight();
+        let mut cursor = None;
+        loop {
+            cursor = MigrateToV3::<Test>::step(cursor, &mut WeightMeter::with_limit(step_weight)).unwrap();
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        // The vote that didn't fit keeps its lock, and the freeze still covers it
+        assert_eq!(crate::VoteLocksFor::<Test>::get(bob).len() as u32, max_votes);
+        assert_eq!(crate::LegacyVoteLocks::<Test>::iter_prefix(bob).count(), 1);
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), 300);
+        assert_ok!(Bullposting::do_try_state());
+
+        // Ending its post unlocks it like any other vote
+        System::set_block_number(voting_period + 2);
+        assert_ok!(Bullposting::try_resolve_voting(RuntimeOrigin::signed(alice), largest.clone()));
+        assert_ok!(Bullposting::try_end_post(RuntimeOrigin::signed(alice), largest));
+        assert_eq!(crate::LegacyVoteLocks::<Test>::iter_prefix(bob).count(), 0);
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), 50);
+    });
+}
+
+#[test]
+fn test_genesis_posts() {
+    use sp_runtime::BuildStorage;
+
+    let alice = 0;
+    let bob = 1;
+    let charlie = 2;
+    let post: Vec<u8> = "Seeded at genesis".into();
+    let short_post: Vec<u8> = "Seeded with a short voting period".into();
+    let post_id = sp_io::hashing::blake2_256(&post);
+    let short_id = sp_io::hashing::blake2_256(&short_post);
+
+    let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(alice, 1001), (bob, 1001), (charlie, 1001)]
+    }.assimilate_storage(&mut t).unwrap();
+    crate::GenesisConfig::<Test> {
+        parameters: None,
+        posts: vec![(post.clone(), alice, 300, None), (short_post.clone(), alice, 200, Some(10))],
+        votes: vec![
+            (bob, post.clone(), 100, crate::VoteChoice::Aye),
+            (charlie, post.clone(), 200, crate::VoteChoice::Split { bull: 50, bear: 150 }),
+            (bob, short_post.clone(), 300, crate::VoteChoice::Nay),
+        ],
+        _config: Default::default(),
+    }.assimilate_storage(&mut t).unwrap();
+
+    execute_with_try_state(t.into(), || {
+        // Posts are submitted with their own voting periods, and the submitter's bonds and rent are held
+        let seeded = crate::Posts::<Test>::get(post_id).unwrap();
+        assert_eq!(seeded.voting_until, 1000);
+        assert_eq!((seeded.bull_votes, seeded.bear_votes), (150, 150));
+        let short = crate::Posts::<Test>::get(short_id).unwrap();
+        assert_eq!(short.voting_until, 10);
+        assert_eq!(short.bear_votes, 300);
+        assert_eq!(Balances::total_balance_on_hold(&alice), 300 + 200 + 2 * 100);
+
+        // Votes are frozen
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &bob), 300);
+        assert_eq!(Balances::balance_frozen(&crate::FreezeReason::Vote.into(), &charlie), 200);
+        assert_eq!(crate::VoteCounts::<Test>::get(post_id), Some(2));
+
+        // Both posts are queued to be resolved when their own voting periods end
+        assert!(crate::ExpiringPosts::<Test>::get(1000).contains(&post_id));
+        assert!(crate::ExpiringPosts::<Test>::get(10).contains(&short_id));
+
+        // And the short post resolves on schedule
+        System::set_block_number(10);
+        Bullposting::on_initialize(10);
+        assert!(crate::Posts::<Test>::get(short_id).unwrap().resolved);
+    });
+}
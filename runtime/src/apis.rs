@@ -42,9 +42,9 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Balance, Block, ConsensusHook, Executive, InherentDataExt, Nonce, ParachainSystem,
-	Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment,
-	SLOT_DURATION, VERSION,
+	AccountId, Balance, Block, BlockNumber, Bullposting, ConsensusHook, Executive, InherentDataExt, Nonce,
+	ParachainSystem, Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System,
+	TransactionPayment, SLOT_DURATION, VERSION,
 };
 
 // we move some impls outside so we can easily use them with `docify`.
@@ -210,6 +210,36 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_parachain_bullposting_runtime_api::BullpostingApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn pot_balance() -> Option<Balance> {
+			Bullposting::pot_balance()
+		}
+
+		fn post(id: [u8; 32]) -> Option<pallet_parachain_bullposting::PostDetails<AccountId, Balance, BlockNumber>> {
+			Bullposting::post_details(id)
+		}
+
+		fn post_by_input(post_input: Vec<u8>) -> Option<pallet_parachain_bullposting::PostDetails<AccountId, Balance, BlockNumber>> {
+			Bullposting::post_details(sp_core::hashing::blake2_256(&post_input))
+		}
+
+		fn votes(who: AccountId) -> Vec<([u8; 32], Balance, pallet_parachain_bullposting::VoteChoice<Balance>)> {
+			Bullposting::account_votes(&who)
+		}
+
+		fn voters(id: [u8; 32]) -> Vec<AccountId> {
+			Bullposting::post_voters(id)
+		}
+
+		fn preview_resolution(id: [u8; 32]) -> Option<pallet_parachain_bullposting::ResolutionPreview<Balance>> {
+			Bullposting::preview_resolution(id)
+		}
+
+		fn parameters() -> pallet_parachain_bullposting::BullpostingParameters<Balance, BlockNumber> {
+			pallet_parachain_bullposting::CurrentParameters::<Runtime>::get()
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
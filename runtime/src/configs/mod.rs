@@ -48,7 +48,7 @@ use polkadot_runtime_common::{
 	xcm_sender::NoPriceForMessageDelivery, BlockHashCount, SlowAdjustingFeeUpdate,
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_runtime::Perbill;
+use sp_runtime::{Perbill, Percent};
 use sp_version::RuntimeVersion;
 use xcm::latest::prelude::BodyId;
 
@@ -56,10 +56,10 @@ use xcm::latest::prelude::BodyId;
 use super::{
 	weights::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight},
 	AccountId, Aura, Balance, Balances, Block, BlockNumber, CollatorSelection, ConsensusHook, Hash,
-	MessageQueue, Nonce, PalletInfo, ParachainSystem, Runtime, RuntimeCall, RuntimeEvent,
-	RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask, Session, SessionKeys,
-	System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, EXISTENTIAL_DEPOSIT, HOURS,
-	MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
+	MessageQueue, MultiBlockMigrations, Nonce, PalletInfo, ParachainSystem, Runtime, RuntimeCall,
+	RuntimeEvent, RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask, Session,
+	SessionKeys, System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, EXISTENTIAL_DEPOSIT,
+	HOURS, MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
 };
 use xcm_config::{RelayLocation, XcmOriginToTransactDispatchOrigin};
 
@@ -123,6 +123,8 @@ impl frame_system::Config for Runtime {
 	/// The action to take on a Runtime Upgrade
 	type OnSetCode = cumulus_pallet_parachain_system::ParachainSetCode<Self>;
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	/// Steps the multi-block migrations, only including inherents in blocks while they run.
+	type MultiBlockMigrator = MultiBlockMigrations;
 }
 
 impl pallet_timestamp::Config for Runtime {
@@ -180,6 +182,27 @@ impl pallet_sudo::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	/// Migrations can use up to 80% of a block, leaving room for the inherents.
+	pub MbmServiceWeight: Weight = Perbill::from_percent(80) * RuntimeBlockWeights::get().max_block;
+}
+
+impl pallet_migrations::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	/// The migrations that are too large for a single block, stepped over as many blocks as they need.
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type Migrations = crate::SteppedMigrations;
+	// Benchmarks need mocked migrations to guarantee that they succeed.
+	#[cfg(feature = "runtime-benchmarks")]
+	type Migrations = pallet_migrations::mock_helpers::MockedMigrations;
+	type CursorMaxLen = ConstU32<65_536>;
+	type IdentifierMaxLen = ConstU32<256>;
+	type MigrationStatusHandler = ();
+	type FailedMigrationHandler = frame_support::migrations::FreezeChainOnFailedMigration;
+	type MaxServiceWeight = MbmServiceWeight;
+	type WeightInfo = pallet_migrations::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
 	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
@@ -313,10 +336,31 @@ parameter_types! {
 	pub const VotingPeriod: BlockNumber = 50;
     pub const BondMinimum: Balance = 1_000_000_000_000_000;
 	pub const VoteMinimum: Balance = 1_000_000_000_000_000;
-    pub const MaxVoters: u32 = 10000;
+	pub const QuorumVotes: Balance = 2_000_000_000_000_000; // posts need at least 2000 in Bullish/Bearish votes to be rewarded or slashed
+	pub const QuorumVoters: u32 = 2; // and at least 2 voters
+	pub const WithdrawalFee: Balance = 10_000_000_000_000; // withdrawing a post before anyone votes costs 10
+    pub const MaxVoters: u32 = 100_000;
+	pub const MaxVotesPerAccount: u32 = 100;
+	pub const BullpostingVoteCollateral: pallet_parachain_bullposting::VoteCollateral =
+		pallet_parachain_bullposting::VoteCollateral::Freeze; // the same tokens can vote on several posts at once
     pub const StorageRent: Balance = 1_000_000_000_000_000;
     pub const MaxInputLength: u32 = 2000;
 	pub const UnfreezeLimit: u32 = 1000;
+	pub const MaxExpiringPerBlock: u32 = 100;
+	pub const MaxResolutionsPerBlock: u32 = 20;
+	pub const MaxExpiryBlocksScanned: u32 = 100;
+	pub const VoterPayouts: bool = false; // set to true to share slashes (and a cut of rewards) among the winning voters
+	pub const VoterRewardCut: Percent = Percent::from_percent(20); // if Alice is rewarded 500, 100 of it goes to the voters
+	pub const CommitReveal: bool = false; // votes are public as they're submitted
+	pub const RevealPeriod: BlockNumber = 20; // commitments can be revealed for 20 blocks after voting ends
+	pub const UnrevealedForfeit: Percent = Percent::from_percent(10); // unrevealed commitments lose 10% of their collateral
+	pub const BullpostingTallyMode: pallet_parachain_bullposting::TallyMode =
+		pallet_parachain_bullposting::TallyMode::Linear; // each token counts once
+	pub const BullpostingPotId: Option<PalletId> = Some(PalletId(*b"bullpost")); // slashes go into this pot and rewards come out of it
+	pub const BullpostingPotShortfall: pallet_parachain_bullposting::ShortfallBehaviour =
+		pallet_parachain_bullposting::ShortfallBehaviour::MintUpToCap; // mint what the pot can't cover
+	pub const ShortfallMintCap: Balance = 10_000_000_000_000_000; // at most 10000 minted per period
+	pub const ShortfallMintPeriod: BlockNumber = 24 * HOURS;
 }
 
 /// Configure the pallet bullposting in pallets/bullposting.
@@ -327,6 +371,7 @@ impl pallet_parachain_bullposting::Config for Runtime {
     type RuntimeHoldReason = RuntimeHoldReason;
     type RuntimeFreezeReason = RuntimeFreezeReason;
     type FreezeIdentifier = RuntimeFreezeReason;
+    type AdminOrigin = EnsureRoot<AccountId>;
     type RewardStyle = RewardStyle;
     type FlatReward = FlatReward;
     type RewardCoefficient = RewardCoefficient;
@@ -337,7 +382,27 @@ impl pallet_parachain_bullposting::Config for Runtime {
     type MaxInputLength = MaxInputLength;
     type BondMinimum = BondMinimum;
 	type VoteMinimum = VoteMinimum;
+	type QuorumVotes = QuorumVotes;
+	type QuorumVoters = QuorumVoters;
+	type WithdrawalFee = WithdrawalFee;
 	type MaxVoters = MaxVoters;
+	type MaxVotesPerAccount = MaxVotesPerAccount;
+	type VoteCollateral = BullpostingVoteCollateral;
 	type StorageRent = StorageRent;
 	type UnfreezeLimit = UnfreezeLimit;
+	type MaxExpiringPerBlock = MaxExpiringPerBlock;
+	type MaxResolutionsPerBlock = MaxResolutionsPerBlock;
+	type MaxExpiryBlocksScanned = MaxExpiryBlocksScanned;
+	type VoterPayouts = VoterPayouts;
+	type VoterRewardCut = VoterRewardCut;
+	type CommitReveal = CommitReveal;
+	type RevealPeriod = RevealPeriod;
+	type UnrevealedForfeit = UnrevealedForfeit;
+	type TallyMode = BullpostingTallyMode;
+	type RewardPolicy = pallet_parachain_bullposting::StylePolicy;
+	type SlashPolicy = pallet_parachain_bullposting::StylePolicy;
+	type PotId = BullpostingPotId;
+	type PotShortfall = BullpostingPotShortfall;
+	type ShortfallMintCap = ShortfallMintCap;
+	type ShortfallMintPeriod = ShortfallMintPeriod;
 }
@@ -1,18 +1,44 @@
 use cumulus_primitives_core::ParaId;
 
 use crate::{
-	AccountId, BalancesConfig, CollatorSelectionConfig, ParachainInfoConfig, PolkadotXcmConfig,
-	RuntimeGenesisConfig, SessionConfig, SessionKeys, SudoConfig, EXISTENTIAL_DEPOSIT,
+	configs::{MaxExpiringPerBlock, MaxResolutionsPerBlock, MaxVoters, MaxVotesPerAccount, UnfreezeLimit},
+	AccountId, BalancesConfig, BullpostingConfig, CollatorSelectionConfig, ParachainInfoConfig,
+	PolkadotXcmConfig, RuntimeGenesisConfig, SessionConfig, SessionKeys, SudoConfig,
+	EXISTENTIAL_DEPOSIT, DAYS, HOURS, UNIT,
 };
-use alloc::{vec, vec::Vec};
+use alloc::{format, vec, vec::Vec};
+use pallet_parachain_bullposting::VoteChoice;
 use parachains_common::{genesis_config_helpers::*, AuraId};
 use serde_json::Value;
-use sp_core::sr25519;
+use sp_core::{hashing::blake2_256, sr25519};
 use sp_genesis_builder::PresetId;
 
 /// The default XCM version to set in genesis config.
 const SAFE_XCM_VERSION: u32 = xcm::prelude::XCM_VERSION;
 
+/// A preset with a board large enough to profile ending posts, see [`stress_genesis`].
+pub const BULLPOSTING_STRESS_PRESET: &str = "bullposting-stress";
+
+/// Generated accounts in the stress preset.
+/// Twice `UnfreezeLimit`, so ending a post they all voted on takes more than two calls.
+const STRESS_ACCOUNTS: u32 = 2 * UnfreezeLimit::get() + 1;
+/// Posts in the stress preset, each submitted by one of the generated accounts.
+/// One more than `MaxResolutionsPerBlock`, so resolving them all takes more than one block.
+const STRESS_POSTS: u32 = MaxResolutionsPerBlock::get() + 1;
+/// Posts in the stress preset voted on by every generated account.
+const STRESS_FULL_POSTS: u32 = 1;
+/// Voters on each of the other posts in the stress preset.
+/// One more than `UnfreezeLimit`, so ending one of them takes several calls.
+const STRESS_VOTERS: u32 = UnfreezeLimit::get() + 1;
+/// Blocks until voting ends on every post in the stress preset.
+const STRESS_VOTING_PERIOD: u64 = 10;
+
+// Every post expires in the same block, and the first `STRESS_VOTERS` accounts vote on every post
+const _: () = assert!(STRESS_POSTS <= MaxExpiringPerBlock::get());
+const _: () = assert!(STRESS_POSTS <= MaxVotesPerAccount::get());
+const _: () = assert!(STRESS_FULL_POSTS <= STRESS_POSTS && STRESS_VOTERS <= STRESS_ACCOUNTS);
+const _: () = assert!(STRESS_ACCOUNTS <= MaxVoters::get());
+
 /// Generate the session keys from individual elements.
 ///
 /// The input must be a tuple of individual keys (a single arg for now since we have just one key).
@@ -20,11 +46,82 @@ pub fn template_session_keys(keys: AuraId) -> SessionKeys {
 	SessionKeys { aura: keys }
 }
 
+/// A few posts with different voting windows and some votes on them, so the dev chains start with a board to look at.
+fn demo_board() -> BullpostingConfig {
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+	let charlie = get_account_id_from_seed::<sr25519::Public>("Charlie");
+	let dave = get_account_id_from_seed::<sr25519::Public>("Dave");
+	let eve = get_account_id_from_seed::<sr25519::Public>("Eve");
+
+	let gm: Vec<u8> = "gm, BullChain is live".into();
+	let moon: Vec<u8> = "DOT will flip ETH this cycle".into();
+	let ferris: Vec<u8> = "Ferris is the best mascot".into();
+
+	BullpostingConfig {
+		posts: vec![
+			// Uses the default voting period
+			(gm.clone(), alice.clone(), 2_000 * UNIT, None),
+			(moon.clone(), bob.clone(), 5_000 * UNIT, Some(HOURS.into())),
+			(ferris.clone(), charlie.clone(), 1_000 * UNIT, Some(DAYS.into())),
+		],
+		votes: vec![
+			(bob.clone(), gm.clone(), 1_000 * UNIT, VoteChoice::Aye),
+			(charlie.clone(), gm, 3_000 * UNIT, VoteChoice::Aye),
+			(alice, moon.clone(), 4_000 * UNIT, VoteChoice::Nay),
+			(dave.clone(), moon.clone(), 2_000 * UNIT, VoteChoice::Aye),
+			(eve.clone(), moon, 2_000 * UNIT, VoteChoice::Abstain),
+			(bob, ferris.clone(), 6_000 * UNIT, VoteChoice::Split { bull: 4_000 * UNIT, bear: 2_000 * UNIT }),
+			(eve, ferris, 1_000 * UNIT, VoteChoice::Nay),
+		],
+		..Default::default()
+	}
+}
+
+/// A board of [`STRESS_POSTS`] posts, all expiring [`STRESS_VOTING_PERIOD`] blocks after genesis. The first
+/// [`STRESS_FULL_POSTS`] are voted on by all [`STRESS_ACCOUNTS`] accounts, and the rest by the first [`STRESS_VOTERS`].
+/// Everything is derived from the indices, so every build of the preset is the same.
+fn stress_board(accounts: &[AccountId]) -> BullpostingConfig {
+	let inputs = (0..STRESS_POSTS)
+		.map(|post| format!("Stress test post #{post}").into_bytes())
+		.collect::<Vec<_>>();
+
+	let posts = inputs
+		.iter()
+		.zip(accounts)
+		.map(|(input, submitter)| (input.clone(), submitter.clone(), 1_000 * UNIT, Some(STRESS_VOTING_PERIOD)))
+		.collect::<Vec<_>>();
+
+	let votes = inputs
+		.iter()
+		.enumerate()
+		.flat_map(|(post, input)| {
+			let voters = if post < STRESS_FULL_POSTS as usize {
+				accounts
+			} else {
+				&accounts[..STRESS_VOTERS as usize]
+			};
+			voters.iter().enumerate().map(move |(voter, account)| {
+				let amount = (1_000 + (voter % 10) as u128 * 100) * UNIT;
+				let vote = match (voter + post) % 4 {
+					0 | 1 => VoteChoice::Aye,
+					2 => VoteChoice::Nay,
+					_ => VoteChoice::Split { bull: amount / 2, bear: amount - amount / 2 },
+				};
+				(account.clone(), input.clone(), amount, vote)
+			})
+		})
+		.collect::<Vec<_>>();
+
+	BullpostingConfig { posts, votes, ..Default::default() }
+}
+
 fn testnet_genesis(
 	invulnerables: Vec<(AccountId, AuraId)>,
 	endowed_accounts: Vec<AccountId>,
 	root: AccountId,
 	id: ParaId,
+	bullposting: BullpostingConfig,
 ) -> Value {
 	let config = RuntimeGenesisConfig {
 		balances: BalancesConfig {
@@ -58,6 +155,7 @@ fn testnet_genesis(
 			..Default::default()
 		},
 		sudo: SudoConfig { key: Some(root) },
+		bullposting,
 		..Default::default()
 	};
 
@@ -93,6 +191,7 @@ fn local_testnet_genesis() -> Value {
 		],
 		get_account_id_from_seed::<sr25519::Public>("Alice"),
 		1000.into(),
+		demo_board(),
 	)
 }
 
@@ -125,6 +224,43 @@ fn development_config_genesis() -> Value {
 		],
 		get_account_id_from_seed::<sr25519::Public>("Alice"),
 		1000.into(),
+		demo_board(),
+	)
+}
+
+fn stress_genesis() -> Value {
+	// Hashed from their index, as deriving this many keys would be slow. They only vote at genesis, so don't need keys.
+	let stress_accounts = (0..STRESS_ACCOUNTS)
+		.map(|i| AccountId::from(blake2_256(format!("Stress//{i}").as_bytes())))
+		.collect::<Vec<_>>();
+	let bullposting = stress_board(&stress_accounts);
+
+	testnet_genesis(
+		// initial collators.
+		vec![
+			(
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				get_collator_keys_from_seed::<AuraId>("Alice"),
+			),
+			(
+				get_account_id_from_seed::<sr25519::Public>("Bob"),
+				get_collator_keys_from_seed::<AuraId>("Bob"),
+			),
+		],
+		[
+			get_account_id_from_seed::<sr25519::Public>("Alice"),
+			get_account_id_from_seed::<sr25519::Public>("Bob"),
+			get_account_id_from_seed::<sr25519::Public>("Charlie"),
+			get_account_id_from_seed::<sr25519::Public>("Dave"),
+			get_account_id_from_seed::<sr25519::Public>("Eve"),
+			get_account_id_from_seed::<sr25519::Public>("Ferdie"),
+		]
+		.into_iter()
+		.chain(stress_accounts)
+		.collect(),
+		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		1000.into(),
+		bullposting,
 	)
 }
 
@@ -133,6 +269,7 @@ pub fn get_preset(id: &PresetId) -> Option<vec::Vec<u8>> {
 	let patch = match id.try_into() {
 		Ok(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET) => local_testnet_genesis(),
 		Ok(sp_genesis_builder::DEV_RUNTIME_PRESET) => development_config_genesis(),
+		Ok(BULLPOSTING_STRESS_PRESET) => stress_genesis(),
 		_ => return None,
 	};
 	Some(
@@ -147,5 +284,33 @@ pub fn preset_names() -> Vec<PresetId> {
 	vec![
 		PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
 		PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+		PresetId::from(BULLPOSTING_STRESS_PRESET),
 	]
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::BuildStorage;
+
+	#[test]
+	fn stress_preset_builds() {
+		let preset = get_preset(&PresetId::from(BULLPOSTING_STRESS_PRESET)).expect("The stress preset exists");
+		let config: RuntimeGenesisConfig =
+			serde_json::from_slice(&preset).expect("The stress preset is a full genesis config");
+		let storage = config.build_storage().expect("The stress preset builds");
+
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			let full_id = blake2_256(b"Stress test post #0");
+			let other_id = blake2_256(format!("Stress test post #{}", STRESS_POSTS - 1).as_bytes());
+
+			assert_eq!(pallet_parachain_bullposting::Posts::<crate::Runtime>::iter_keys().count(), STRESS_POSTS as usize);
+			assert_eq!(pallet_parachain_bullposting::VoteCounts::<crate::Runtime>::get(full_id), Some(STRESS_ACCOUNTS));
+			assert_eq!(pallet_parachain_bullposting::VoteCounts::<crate::Runtime>::get(other_id), Some(STRESS_VOTERS));
+			assert_eq!(
+				pallet_parachain_bullposting::ExpiringPosts::<crate::Runtime>::get(STRESS_VOTING_PERIOD as u32).len(),
+				STRESS_POSTS as usize
+			);
+		});
+	}
+}
@@ -36,6 +36,8 @@ pub use sp_runtime::{MultiAddress, Perbill, Permill};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 
+pub use genesis_config_presets::BULLPOSTING_STRESS_PRESET;
+
 use weights::ExtrinsicBaseWeight;
 
 /// Alias to 512-bit hash when used in the context of a transaction signature on the chain.
@@ -94,9 +96,20 @@ pub type UncheckedExtrinsic =
 /// All migrations of the runtime, aside from the ones declared in the pallets.
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
+/// Multi-block migrations are in [`SteppedMigrations`] instead.
 #[allow(unused_parens)]
 type Migrations = ();
 
+/// The multi-block migrations of the runtime, stepped by `pallet_migrations` after an upgrade.
+///
+/// These upgrade the bullposting pallet's storage from version 0 to 3 in order, each only running while the pallet is
+/// on the version it upgrades from. Bump `spec_version` when adding one.
+pub type SteppedMigrations = (
+	pallet_parachain_bullposting::migrations::v1::MigrateToPostVoters<Runtime>,
+	pallet_parachain_bullposting::migrations::v2::MigrateToV2<Runtime>,
+	pallet_parachain_bullposting::migrations::v3::MigrateToV3<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -168,7 +181,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("parachain-bullposting-runtime"),
 	impl_name: create_runtime_str!("parachain-bullposting-runtime"),
 	authoring_version: 1,
-	spec_version: 4,
+	spec_version: 5,
 	impl_version: 0,
 	apis: apis::RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -272,6 +285,8 @@ mod runtime {
 	pub type Timestamp = pallet_timestamp;
 	#[runtime::pallet_index(3)]
 	pub type ParachainInfo = parachain_info;
+	#[runtime::pallet_index(4)]
+	pub type MultiBlockMigrations = pallet_migrations;
 
 	// Monetary stuff.
 	#[runtime::pallet_index(10)]